@@ -0,0 +1,203 @@
+use crate::graph::Cycle;
+use std::fmt::{self, Display, Formatter};
+
+// what a part returns, integers are widened so every day prints the same way
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Wide(u128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Wide(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident: $($t:ty),*) => {$(
+        impl From<$t> for Answer {
+            fn from(n: $t) -> Answer {
+                Answer::$variant(n.into())
+            }
+        }
+    )*};
+}
+
+answer_from!(Signed: i32, i64);
+answer_from!(Unsigned: u32, u64);
+answer_from!(Wide: u128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Answer {
+        match u64::try_from(n) {
+            Ok(n) => Answer::Unsigned(n),
+            Err(_) => Answer::Wide(n as u128),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Answer {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Answer {
+        Answer::Text(text.to_string())
+    }
+}
+
+// an integer operation that didn't fit its type, `operation` says where
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub operation: &'static str,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "arithmetic overflow in {}", self.operation)
+    }
+}
+
+// why input that parsed still has no answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsolvable {
+    Overflow(Overflow),
+    // things that have to come before themselves, widened like the answers
+    Cycle(Cycle<i64>),
+    // a process that never finishes, `what` says which
+    Endless { what: &'static str },
+}
+
+impl Display for Unsolvable {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Unsolvable::Overflow(overflow) => write!(f, "{overflow}"),
+            Unsolvable::Cycle(cycle) => write!(f, "no valid order, {cycle}"),
+            Unsolvable::Endless { what } => write!(f, "{what} never ends"),
+        }
+    }
+}
+
+impl From<Overflow> for Unsolvable {
+    fn from(overflow: Overflow) -> Unsolvable {
+        Unsolvable::Overflow(overflow)
+    }
+}
+
+impl From<Cycle<i32>> for Unsolvable {
+    fn from(Cycle(nodes): Cycle<i32>) -> Unsolvable {
+        Unsolvable::Cycle(Cycle(nodes.into_iter().map(i64::from).collect()))
+    }
+}
+
+// for the main functions, the value or why there is none
+pub fn show<T: Display, E: Display>(result: Result<T, E>) -> String {
+    match result {
+        Ok(value) => value.to_string(),
+        Err(error) => error.to_string(),
+    }
+}
+
+pub trait Checked: Copy {
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! checked {
+    ($($t:ty),*) => {$(
+        impl Checked for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            fn checked_add(self, other: $t) -> Option<$t> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: $t) -> Option<$t> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: $t) -> Option<$t> {
+                <$t>::checked_mul(self, other)
+            }
+        }
+    )*};
+}
+
+checked!(i32, i64, u32, u64, usize, u128);
+
+pub fn add<T: Checked>(a: T, b: T, operation: &'static str) -> Result<T, Overflow> {
+    a.checked_add(b).ok_or(Overflow { operation })
+}
+
+pub fn sub<T: Checked>(a: T, b: T, operation: &'static str) -> Result<T, Overflow> {
+    a.checked_sub(b).ok_or(Overflow { operation })
+}
+
+pub fn mul<T: Checked>(a: T, b: T, operation: &'static str) -> Result<T, Overflow> {
+    a.checked_mul(b).ok_or(Overflow { operation })
+}
+
+// sums and products that stop at the first overflow
+pub trait CheckedIterator<T: Checked>: Iterator<Item = T> + Sized {
+    fn checked_sum(mut self, operation: &'static str) -> Result<T, Overflow> {
+        self.try_fold(T::ZERO, |total, n| add(total, n, operation))
+    }
+
+    fn checked_product(mut self, operation: &'static str) -> Result<T, Overflow> {
+        self.try_fold(T::ONE, |total, n| mul(total, n, operation))
+    }
+}
+
+impl<T: Checked, I: Iterator<Item = T>> CheckedIterator<T> for I {}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(7usize), Answer::Unsigned(7));
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".to_string()));
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!([1u8 as i32, 2, 3].into_iter().checked_sum("test"), Ok(6));
+        assert_eq!(
+            [i32::MAX, 1].into_iter().checked_sum("test"),
+            Err(Overflow { operation: "test" })
+        );
+        assert_eq!([2u64, 3, 4].into_iter().checked_product("test"), Ok(24));
+        assert!([u64::MAX, 2].into_iter().checked_product("test").is_err());
+        assert_eq!(mul(3usize, 4, "test"), Ok(12));
+        assert_eq!(sub(3i64, 5, "test"), Ok(-2));
+        assert!(sub(0u32, 1, "test").is_err());
+        let error = add(u32::MAX, 1, "fence cost").unwrap_err();
+        assert_eq!(error.to_string(), "arithmetic overflow in fence cost");
+        assert_eq!(Unsolvable::from(error), Unsolvable::Overflow(error));
+    }
+
+    #[test]
+    fn cycle() {
+        let unsolvable = Unsolvable::from(Cycle(vec![1, 2]));
+        assert_eq!(unsolvable, Unsolvable::Cycle(Cycle(vec![1i64, 2])));
+        assert_eq!(unsolvable.to_string(), "no valid order, cycle 1 -> 2 -> 1");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::input::normalize;
+use crate::solution::Solution;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions { runs: 5, warmup: 1 }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+pub fn time<T>(options: &BenchOptions, mut f: impl FnMut() -> T) -> Stats {
+    assert!(options.runs > 0, "a benchmark needs at least one run");
+    for _ in 0..options.warmup {
+        black_box(f());
+    }
+    let samples = (0..options.runs)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::from_samples(samples)
+}
+
+pub fn bench<S: Solution>(input: &str, options: &BenchOptions) -> Result<Timings, String> {
+    let input = normalize(input);
+    let parsed = S::parse(&input).map_err(|error| error.to_string())?;
+    Ok(Timings {
+        parse: time(options, || S::parse(&input)),
+        part1: time(options, || S::part1(&parsed)),
+        part2: time(options, || S::part2(&parsed)),
+    })
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:>10}", format!("{duration:.2?}"))
+}
+
+pub fn format_table(rows: &[(u8, Timings)]) -> String {
+    let mut table = format!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}\n",
+        "day", "phase", "min", "median", "max"
+    );
+    let mut total = Duration::ZERO;
+    for (day, timings) in rows {
+        for (phase, stats) in [
+            ("parse", timings.parse),
+            ("part1", timings.part1),
+            ("part2", timings.part2),
+        ] {
+            table += &format!(
+                "{day:>3}  {phase:<5}  {}  {}  {}\n",
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max)
+            );
+            total += stats.median;
+        }
+    }
+    table += &format!("total median {}\n", format_duration(total).trim_start());
+    table
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples = [5, 1, 4, 2, 3].map(Duration::from_millis).to_vec();
+        let stats = Stats::from_samples(samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn time_runs_warmup_and_samples() {
+        let mut calls = 0;
+        let options = BenchOptions { runs: 3, warmup: 2 };
+        let stats = time(&options, || calls += 1);
+        assert_eq!(calls, 5);
+        assert!(stats.min <= stats.median && stats.median <= stats.max);
+    }
+
+    #[test]
+    fn table_has_a_row_per_phase() {
+        let stats = Stats {
+            min: Duration::from_micros(1),
+            median: Duration::from_micros(2),
+            max: Duration::from_micros(3),
+        };
+        let timings = Timings {
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        };
+        let table = format_table(&[(1, timings), (2, timings)]);
+        assert_eq!(table.lines().count(), 8);
+        assert!(table.contains("  2  part2"));
+        assert!(table.ends_with("total median 12.00µs\n"));
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+
+pub const ANSWERS_PATH: &str = "artifacts/answers.txt";
+
+// one "<day> <part> <answer>" per line, lines starting with # are comments
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, u8), String>,
+    // the parsed text, printing keeps its comments and layout
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Line {
+    Text(String),
+    // printed as it was unless the answer changed since
+    Entry {
+        key: (u8, u8),
+        answer: String,
+        text: String,
+    },
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let mut answers = BTreeMap::new();
+        let mut lines = Vec::new();
+        for (index, text) in text.lines().enumerate() {
+            let line = text.trim();
+            if line.is_empty() || line.starts_with('#') {
+                lines.push(Line::Text(text.to_owned()));
+                continue;
+            }
+            let invalid = || format!("invalid answer on line {}: {line:?}", index + 1);
+            let mut fields = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid());
+            };
+            let day = day.parse().map_err(|_| invalid())?;
+            let part = part.parse().map_err(|_| invalid())?;
+            let answer = answer.trim().to_owned();
+            answers.insert((day, part), answer.clone());
+            lines.push(Line::Entry {
+                key: (day, part),
+                answer,
+                text: text.to_owned(),
+            });
+        }
+        Ok(Answers { answers, lines })
+    }
+
+    pub fn load(path: &str) -> Result<Answers, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(error) => Err(format!("can't read {path}: {error}")),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_string()).map_err(|error| format!("can't write {path}: {error}"))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+}
+
+impl Display for Answers {
+    // new answers go after the parsed lines
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut printed = BTreeSet::new();
+        for line in &self.lines {
+            match line {
+                Line::Text(text) => writeln!(f, "{text}")?,
+                Line::Entry { key, answer, text } => {
+                    printed.insert(*key);
+                    match self.answers.get(key) {
+                        Some(current) if current != answer => {
+                            writeln!(f, "{} {} {current}", key.0, key.1)?
+                        }
+                        _ => writeln!(f, "{text}")?,
+                    }
+                }
+            }
+        }
+        for ((day, part), answer) in &self.answers {
+            if !printed.contains(&(*day, *part)) {
+                writeln!(f, "{day} {part} {answer}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass(String),
+    Fail { expected: String, actual: String },
+    Missing(String),
+}
+
+impl Outcome {
+    pub fn compare(expected: Option<&str>, actual: String) -> Outcome {
+        match expected {
+            None => Outcome::Missing(actual),
+            Some(expected) if expected == actual => Outcome::Pass(actual),
+            Some(expected) => Outcome::Fail {
+                expected: expected.to_owned(),
+                actual,
+            },
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass(answer) => write!(f, "PASS {answer}"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL expected {expected}, got {actual}")
+            }
+            Outcome::Missing(answer) => write!(f, "MISSING got {answer}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn parse_and_print() {
+        let text = "# day part answer\n1 1 11\n\n1 2 31\n13 1 480\n";
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(13, 1), Some("480"));
+        assert_eq!(answers.get(13, 2), None);
+        assert_eq!(answers.to_string(), text);
+        assert!(Answers::parse("1 x 11").is_err());
+        assert!(Answers::parse("1 1").is_err());
+    }
+
+    #[test]
+    fn record_keeps_comments() {
+        let path = env::temp_dir().join(format!("day1q1-answers-{}", process::id()));
+        let path = path.to_str().unwrap();
+        let text = "# day part answer\n1 1 11\n\n# day 13 is slow\n13 1 480  \n";
+        fs::write(path, text).unwrap();
+        let mut answers = Answers::load(path).unwrap();
+        answers.insert(13, 1, "480".into());
+        answers.insert(1, 1, "12".into());
+        answers.insert(2, 1, "2".into());
+        answers.save(path).unwrap();
+        let expected = "# day part answer\n1 1 12\n\n# day 13 is slow\n13 1 480  \n2 1 2\n";
+        assert_eq!(fs::read_to_string(path).unwrap(), expected);
+        assert_eq!(Answers::load(path).unwrap().get(2, 1), Some("2"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn compare_answers() {
+        assert_eq!(
+            Outcome::compare(Some("11"), "11".into()),
+            Outcome::Pass("11".into())
+        );
+        assert_eq!(
+            Outcome::compare(Some("11"), "12".into()).to_string(),
+            "FAIL expected 11, got 12"
+        );
+        assert_eq!(
+            Outcome::compare(None, "12".into()),
+            Outcome::Missing("12".into())
+        );
+    }
+
+    #[test]
+    fn checked_in_answers_parse() {
+        let answers = Answers::load(ANSWERS_PATH).unwrap();
+        assert!(answers.get(1, 1).is_some());
+    }
+}
@@ -1,50 +1,87 @@
+use crate::answer::{self, Answer, CheckedIterator, Overflow, Unsolvable};
+use crate::error::ParseError;
+use crate::solution::Solution;
 use itertools::sorted;
-use std::fs;
 
-pub fn main() {
-    let file_path = "artifacts/input_day1.txt";
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let lists = parse_string(&input);
-    let result = calculate_similarity(&lists.0, &lists.1);
-    println!("{}", result)
+const DAY: u8 = 1;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(lists: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(calculate_distance(&lists.0, &lists.1)?.into())
+    }
+    fn part2(lists: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(calculate_similarity(&lists.0, &lists.1)?.into())
+    }
 }
 
-fn parse_string(input: &str) -> (Vec<i32>, Vec<i32>) {
+pub fn main(input: &str) -> Result<(), ParseError> {
+    let lists = parse_string(input)?;
+    println!("{}", answer::show(calculate_distance(&lists.0, &lists.1)));
+    println!("{}", answer::show(calculate_similarity(&lists.0, &lists.1)));
+    Ok(())
+}
+
+pub fn parse_string(input: &str) -> Result<(Vec<i32>, Vec<i32>), ParseError> {
     let mut column1: Vec<i32> = Vec::new();
     let mut column2: Vec<i32> = Vec::new();
 
-    for line in input.lines() {
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
         let numbers: Vec<i32> = line
             .split_whitespace()
-            .map(|num| num.parse::<i32>().expect("Invalid number"))
-            .collect();
+            .map(|num| {
+                num.parse::<i32>()
+                    .map_err(|_| ParseError::new(DAY, input, num, "invalid number"))
+            })
+            .collect::<Result<_, _>>()?;
 
-        if let [first, second] = &numbers[..] {
-            column1.push(*first);
-            column2.push(*second);
-        }
+        let [first, second] = numbers[..] else {
+            return Err(ParseError::new(
+                DAY,
+                input,
+                line.trim(),
+                "expected two numbers",
+            ));
+        };
+        column1.push(first);
+        column2.push(second);
     }
-    (column1, column2)
+    Ok((column1, column2))
 }
 
-fn calculate_distance(list1: &Vec<i32>, list2: &Vec<i32>) -> i32 {
+pub fn calculate_distance(list1: &[i32], list2: &[i32]) -> Result<i32, Overflow> {
     let list1_sorted = sorted(list1);
     let mut list2_sorted = sorted(list2);
     let mut result: i32 = 0;
     for i in list1_sorted {
-        result += (i - list2_sorted.next().unwrap()).abs();
+        let distance = i.abs_diff(*list2_sorted.next().unwrap());
+        let distance = i32::try_from(distance).map_err(|_| Overflow {
+            operation: "distance",
+        })?;
+        result = answer::add(result, distance, "distance")?;
     }
 
-    result
+    Ok(result)
 }
 
-fn calculate_similarity(list1: &Vec<i32>, list2: &Vec<i32>) -> i32 {
+pub fn calculate_similarity(list1: &[i32], list2: &[i32]) -> Result<i32, Overflow> {
     let mut result: i32 = 0;
     for i in list1 {
-        result += list2.iter().filter(|x| *x == i).sum::<i32>();
+        let score = list2
+            .iter()
+            .filter(|x| *x == i)
+            .copied()
+            .checked_sum("similarity")?;
+        result = answer::add(result, score, "similarity")?;
     }
 
-    result
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -61,17 +98,30 @@ mod tests {
             vec![60236, 27507, 69810, 60448],
             vec![87497, 18604, 73952, 56269],
         );
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_string("1   2\n3   x4\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.text, "x4");
+        let error = parse_string("1   2\n3   4   5\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected two numbers");
     }
 
     #[test]
     fn test_calculate_distance() {
-        let result = calculate_distance(&vec![3, 1, 5, 2], &vec![5, 0, 2, 4]);
-        assert_eq!(result, 2);
+        let result = calculate_distance(&[3, 1, 5, 2], &[5, 0, 2, 4]);
+        assert_eq!(result, Ok(2));
+        assert!(calculate_distance(&[i32::MIN], &[1]).is_err());
     }
     #[test]
     fn test_calculate_similarity() {
-        let result = calculate_similarity(&vec![3, 4, 2, 1, 3, 3], &vec![4, 3, 5, 3, 9, 3]);
-        assert_eq!(result, 31);
+        let result = calculate_similarity(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]);
+        assert_eq!(result, Ok(31));
+        let error = calculate_similarity(&[i32::MAX], &[i32::MAX, i32::MAX]).unwrap_err();
+        assert_eq!(error.operation, "similarity");
     }
 }
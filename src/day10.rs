@@ -1,40 +1,115 @@
+use crate::answer::{Answer, Unsolvable};
+use crate::day10_part2;
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::pathfinding;
+use crate::ppm::{Palette, Rgb};
+use crate::render::{Colour, Glyph, Render};
+use crate::solution::Solution;
 use std::collections::HashSet;
-use std::fs;
-use std::sync::OnceLock;
-static GRID: OnceLock<Vec<Vec<u32>>> = OnceLock::new();
-const FILE_NAME: &str = "input_day10.txt";
-
-pub fn main() {
-    let mut probes = Probe::generate_probes(get_grid());
-    let result: usize = probes
+const DAY: u8 = 10;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(grid: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(sum_trailheads(grid).into())
+    }
+    fn part2(grid: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(day10_part2::sum_trailheads(grid).into())
+    }
+}
+
+impl Render for Day10 {
+    const PALETTE: Palette = Palette::DEFAULT
+        .with_empty(Rgb(92, 64, 51))
+        .with(Colour::Green, Rgb(34, 139, 34))
+        .with(Colour::Red, Rgb(250, 250, 250));
+
+    fn render(
+        grid: &Self::Input,
+        _every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        frame(&trails_canvas(grid));
+        Ok(())
+    }
+}
+
+// heights on a trail, everything else is left out
+fn trails_canvas(grid: &Grid<u32>) -> Grid<Glyph> {
+    let trails = trail_cells(grid);
+    let mut canvas = grid.map(|_| Glyph::new('.'));
+    for point in trails {
+        let height = grid[point];
+        let colour = match height {
+            0 => Colour::Yellow,
+            9 => Colour::Red,
+            _ => Colour::Green,
+        };
+        canvas[point] = Glyph::new(char::from_digit(height, 10).unwrap()).fg(colour);
+    }
+    canvas
+}
+
+// cells that can be reached uphill from a trailhead and lead up to a top
+fn trail_cells(grid: &Grid<u32>) -> HashSet<Point> {
+    let uphill = |&point: &Point| {
+        grid.neighbours4(point)
+            .filter(move |&next| grid[next] == grid[point] + 1)
+    };
+    let downhill = |&point: &Point| {
+        grid.neighbours4(point)
+            .filter(move |&next| grid[next] + 1 == grid[point])
+    };
+    let from_trailheads = reached_from(grid, 0, uphill);
+    let from_tops = reached_from(grid, 9, downhill);
+    from_trailheads.intersection(&from_tops).copied().collect()
+}
+
+fn reached_from<I: IntoIterator<Item = Point>>(
+    grid: &Grid<u32>,
+    height: u32,
+    neighbours: impl FnMut(&Point) -> I + Copy,
+) -> HashSet<Point> {
+    grid.iter()
+        .filter(|&(_, &value)| value == height)
+        .flat_map(|(start, _)| {
+            pathfinding::bfs(start, neighbours)
+                .reached()
+                .map(|(&point, _)| point)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
+    let grid = parse_string(input)?;
+    println!("{}", sum_trailheads(&grid));
+    Ok(())
+}
+
+pub fn sum_trailheads(grid: &Grid<u32>) -> usize {
+    let mut probes = Probe::generate_probes(grid);
+    probes
         .iter_mut()
         .map(|probe| {
-            probe.solve();
+            probe.solve(grid);
             probe.count_trailheads()
         })
-        .sum();
-    println!("{}", result)
+        .sum()
 }
 
-fn get_grid() -> &'static Vec<Vec<u32>> {
-    GRID.get_or_init(|| {
-        let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-        let input_raw =
-            fs::read_to_string(file_path).expect("Should have been able to read the file");
-        input_raw
-            .split_whitespace()
-            .map(|str| str.chars().map(|c| c.to_digit(10).unwrap()).collect())
-            .collect()
-    })
-}
-fn get_value(x: usize, y: usize) -> Option<u32> {
-    if y >= get_grid().len() || x >= get_grid()[0].len() {
-        return None;
-    }
-    Some(get_grid()[y][x])
+pub fn parse_string(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(DAY, input, |c| c.to_digit(10).ok_or("invalid digit"))
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct Cell {
     x: usize,
     y: usize,
@@ -42,20 +117,14 @@ struct Cell {
 }
 
 impl Cell {
-    fn try_new(x: usize, y: usize) -> Option<Cell> {
-        get_value(x, y).map(|value| Cell { x, y, value })
-    }
-    fn search_neighbors(&self, value: u32) -> HashSet<Cell> {
-        HashSet::from([
-            Cell::try_new(self.x.saturating_sub(1), self.y),
-            Cell::try_new(self.x, self.y.saturating_sub(1)),
-            Cell::try_new(self.x + 1, self.y),
-            Cell::try_new(self.x, self.y + 1),
-        ])
-        .into_iter()
-        .flatten()
-        .filter(|cell| cell.value == value)
-        .collect()
+    fn try_new(grid: &Grid<u32>, x: usize, y: usize) -> Option<Cell> {
+        grid.get((x, y)).map(|&value| Cell { x, y, value })
+    }
+    fn search_neighbors(&self, grid: &Grid<u32>, value: u32) -> HashSet<Cell> {
+        grid.neighbours4((self.x, self.y))
+            .filter_map(|(x, y)| Cell::try_new(grid, x, y))
+            .filter(|cell| cell.value == value)
+            .collect()
     }
 }
 #[derive(Debug, PartialEq, Eq)]
@@ -71,20 +140,15 @@ enum Status {
     Error,
 }
 impl Probe {
-    fn generate_probes(grid: &[Vec<u32>]) -> Vec<Probe> {
-        (0..grid.len())
-            .flat_map(|y| {
-                grid[y]
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, value)| **value == 0)
-                    .map(move |(x, _)| Probe::new(x, y))
-            })
+    fn generate_probes(grid: &Grid<u32>) -> Vec<Probe> {
+        grid.iter()
+            .filter(|(_, value)| **value == 0)
+            .map(|((x, y), _)| Probe::new(grid, x, y))
             .collect()
     }
 
-    fn new(x: usize, y: usize) -> Probe {
-        let init_cell = Cell::try_new(x, y).unwrap();
+    fn new(grid: &Grid<u32>, x: usize, y: usize) -> Probe {
+        let init_cell = Cell::try_new(grid, x, y).unwrap();
         let status = match init_cell.value {
             0 => Status::Pending,
             _ => Status::Error,
@@ -95,21 +159,25 @@ impl Probe {
         }
     }
 
-    fn solve(&mut self) {
+    // keeps every top reachable from the cells
+    fn solve(&mut self, grid: &Grid<u32>) {
         self.status = Status::Running;
-        for step in 1..=9 {
-            self.cells = self.search_all_neighbors(step)
+        let mut tops = HashSet::new();
+        for cell in &self.cells {
+            let uphill = |cell: &Cell| cell.search_neighbors(grid, cell.value + 1);
+            let search = pathfinding::bfs(cell.clone(), uphill);
+            tops.extend(
+                search
+                    .reached()
+                    .map(|(cell, _)| cell)
+                    .filter(|cell| cell.value == 9)
+                    .cloned(),
+            );
         }
+        self.cells = tops;
         self.status = Status::Ended;
     }
 
-    fn search_all_neighbors(&self, value: u32) -> HashSet<Cell> {
-        self.cells
-            .iter()
-            .flat_map(|cell| cell.search_neighbors(value))
-            .collect()
-    }
-
     fn count_trailheads(&self) -> usize {
         match self.status {
             Status::Ended => self.cells.len(),
@@ -121,49 +189,70 @@ impl Probe {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::render::draw;
+    use std::fs;
+
+    fn load_grid() -> Grid<u32> {
+        let input = fs::read_to_string("artifacts/input_files/input_day10.txt").unwrap();
+        parse_string(&input).unwrap()
+    }
+
     #[test]
     fn new_cell() {
-        let cell = Cell::try_new(0, 0);
+        let grid = load_grid();
+        let cell = Cell::try_new(&grid, 0, 0);
         assert_eq!(cell.unwrap().value, 5);
-        let cell = Cell::try_new(1, 0);
+        let cell = Cell::try_new(&grid, 1, 0);
         assert_eq!(cell.unwrap().value, 6);
-        let cell = Cell::try_new(0, 1);
+        let cell = Cell::try_new(&grid, 0, 1);
         assert_eq!(cell.unwrap().value, 4);
-        let cell = Cell::try_new(1, 1);
+        let cell = Cell::try_new(&grid, 1, 1);
         assert_eq!(cell.unwrap().value, 1);
     }
 
     #[test]
     fn test_search_neighbors() {
-        let cell = Cell::try_new(0, 0).unwrap();
-        let result = cell.search_neighbors(6);
-        let expected = HashSet::from([Cell::try_new(1, 0).unwrap()]);
+        let grid = load_grid();
+        let cell = Cell::try_new(&grid, 0, 0).unwrap();
+        let result = cell.search_neighbors(&grid, 6);
+        let expected = HashSet::from([Cell::try_new(&grid, 1, 0).unwrap()]);
         assert_eq!(result, expected);
-        let cell = Cell::try_new(0, 0).unwrap();
-        let result = cell.search_neighbors(1);
+        let cell = Cell::try_new(&grid, 0, 0).unwrap();
+        let result = cell.search_neighbors(&grid, 1);
         let expected = HashSet::new();
         assert_eq!(result, expected);
     }
 
     #[test]
     fn run_prob_no_split() {
-        let mut prob = Probe::new(12, 7);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 12, 7);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 1);
     }
     #[test]
     fn run_prob_split_once() {
-        let mut prob = Probe::new(0, 10);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 0, 10);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 1);
-        let mut prob = Probe::new(34, 0);
-        prob.solve();
+        let mut prob = Probe::new(&grid, 34, 0);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 1);
     }
     #[test]
     fn run_prob_split_multiple() {
-        let mut prob = Probe::new(16, 0);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 16, 0);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 3);
     }
+
+    #[test]
+    fn render_trails() {
+        let grid = parse_string("0123\n7654\n8909").unwrap();
+        let canvas = trails_canvas(&grid);
+        assert_eq!(draw(&canvas, false), "0123\n7654\n89..\n");
+        assert_eq!(canvas[(1, 2)].foreground, Some(Colour::Red));
+    }
 }
@@ -1,40 +1,31 @@
-use std::fs;
-use std::sync::OnceLock;
-static GRID: OnceLock<Vec<Vec<u32>>> = OnceLock::new();
-const FILE_NAME: &str = "input_day10.txt";
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::pathfinding;
+const DAY: u8 = 10;
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let mut probes = Probe::generate_probes(get_grid());
-    let result: usize = probes
+    let grid = parse_string(input)?;
+    println!("{}", sum_trailheads(&grid));
+    Ok(())
+}
+
+pub fn sum_trailheads(grid: &Grid<u32>) -> usize {
+    let mut probes = Probe::generate_probes(grid);
+    probes
         .iter_mut()
         .map(|probe| {
-            probe.solve();
+            probe.solve(grid);
             probe.count_trailheads()
         })
-        .sum();
-    println!("{}", result)
+        .sum()
 }
 
-fn get_grid() -> &'static Vec<Vec<u32>> {
-    GRID.get_or_init(|| {
-        let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-        let input_raw =
-            fs::read_to_string(file_path).expect("Should have been able to read the file");
-        input_raw
-            .split_whitespace()
-            .map(|str| str.chars().map(|c| c.to_digit(10).unwrap()).collect())
-            .collect()
-    })
-}
-fn get_value(x: usize, y: usize) -> Option<u32> {
-    if y >= get_grid().len() || x >= get_grid()[0].len() {
-        return None;
-    }
-    Some(get_grid()[y][x])
+fn parse_string(input: &str) -> Result<Grid<u32>, ParseError> {
+    Grid::parse(DAY, input, |c| c.to_digit(10).ok_or("invalid digit"))
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 struct Cell {
     x: usize,
     y: usize,
@@ -42,20 +33,14 @@ struct Cell {
 }
 
 impl Cell {
-    fn try_new(x: usize, y: usize) -> Option<Cell> {
-        get_value(x, y).map(|value| Cell { x, y, value })
+    fn try_new(grid: &Grid<u32>, x: usize, y: usize) -> Option<Cell> {
+        grid.get((x, y)).map(|&value| Cell { x, y, value })
     }
-    fn search_neighbors(&self, value: u32) -> Vec<Cell> {
-        Vec::from([
-            Cell::try_new(self.x.saturating_sub(1), self.y),
-            Cell::try_new(self.x, self.y.saturating_sub(1)),
-            Cell::try_new(self.x + 1, self.y),
-            Cell::try_new(self.x, self.y + 1),
-        ])
-        .into_iter()
-        .flatten()
-        .filter(|cell| cell.value == value)
-        .collect()
+    fn search_neighbors(&self, grid: &Grid<u32>, value: u32) -> Vec<Cell> {
+        grid.neighbours4((self.x, self.y))
+            .filter_map(|(x, y)| Cell::try_new(grid, x, y))
+            .filter(|cell| cell.value == value)
+            .collect()
     }
 }
 #[derive(Debug, PartialEq, Eq)]
@@ -71,20 +56,15 @@ enum Status {
     Error,
 }
 impl Probe {
-    fn generate_probes(grid: &[Vec<u32>]) -> Vec<Probe> {
-        (0..grid.len())
-            .flat_map(|y| {
-                grid[y]
-                    .iter()
-                    .enumerate()
-                    .filter(|(_, value)| **value == 0)
-                    .map(move |(x, _)| Probe::new(x, y))
-            })
+    fn generate_probes(grid: &Grid<u32>) -> Vec<Probe> {
+        grid.iter()
+            .filter(|(_, value)| **value == 0)
+            .map(|((x, y), _)| Probe::new(grid, x, y))
             .collect()
     }
 
-    fn new(x: usize, y: usize) -> Probe {
-        let init_cell = Cell::try_new(x, y).unwrap();
+    fn new(grid: &Grid<u32>, x: usize, y: usize) -> Probe {
+        let init_cell = Cell::try_new(grid, x, y).unwrap();
         let status = match init_cell.value {
             0 => Status::Pending,
             _ => Status::Error,
@@ -95,21 +75,26 @@ impl Probe {
         }
     }
 
-    fn solve(&mut self) {
+    // keeps the top of every trail starting at the cells, a top can end several trails
+    fn solve(&mut self, grid: &Grid<u32>) {
         self.status = Status::Running;
-        for step in 1..=9 {
-            self.cells = self.search_all_neighbors(step)
+        let mut tops = Vec::new();
+        for cell in &self.cells {
+            let uphill = |cell: &Cell| {
+                let higher = cell.search_neighbors(grid, cell.value + 1);
+                higher.into_iter().map(|cell| (cell, 1))
+            };
+            // every step climbs by one so all trails to the tops are equally long
+            let is_top = |cell: &Cell| cell.value == 9;
+            if let Some((trails, _)) = pathfinding::all_shortest_paths(cell.clone(), uphill, is_top)
+            {
+                tops.extend(trails.into_iter().filter_map(|mut trail| trail.pop()));
+            }
         }
+        self.cells = tops;
         self.status = Status::Ended;
     }
 
-    fn search_all_neighbors(&self, value: u32) -> Vec<Cell> {
-        self.cells
-            .iter()
-            .flat_map(|cell| cell.search_neighbors(value))
-            .collect()
-    }
-
     fn count_trailheads(&self) -> usize {
         match self.status {
             Status::Ended => self.cells.len(),
@@ -121,49 +106,61 @@ impl Probe {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use std::fs;
+
+    fn load_grid() -> Grid<u32> {
+        let input = fs::read_to_string("artifacts/input_files/input_day10.txt").unwrap();
+        parse_string(&input).unwrap()
+    }
+
     #[test]
     fn new_cell() {
-        let cell = Cell::try_new(0, 0);
+        let grid = load_grid();
+        let cell = Cell::try_new(&grid, 0, 0);
         assert_eq!(cell.unwrap().value, 5);
-        let cell = Cell::try_new(1, 0);
+        let cell = Cell::try_new(&grid, 1, 0);
         assert_eq!(cell.unwrap().value, 6);
-        let cell = Cell::try_new(0, 1);
+        let cell = Cell::try_new(&grid, 0, 1);
         assert_eq!(cell.unwrap().value, 4);
-        let cell = Cell::try_new(1, 1);
+        let cell = Cell::try_new(&grid, 1, 1);
         assert_eq!(cell.unwrap().value, 1);
     }
 
     #[test]
     fn test_search_neighbors() {
-        let cell = Cell::try_new(0, 0).unwrap();
-        let result = cell.search_neighbors(6);
-        let expected = Vec::from([Cell::try_new(1, 0).unwrap()]);
+        let grid = load_grid();
+        let cell = Cell::try_new(&grid, 0, 0).unwrap();
+        let result = cell.search_neighbors(&grid, 6);
+        let expected = Vec::from([Cell::try_new(&grid, 1, 0).unwrap()]);
         assert_eq!(result, expected);
-        let cell = Cell::try_new(0, 0).unwrap();
-        let result = cell.search_neighbors(1);
+        let cell = Cell::try_new(&grid, 0, 0).unwrap();
+        let result = cell.search_neighbors(&grid, 1);
         let expected = Vec::new();
         assert_eq!(result, expected);
     }
 
     #[test]
     fn run_prob_no_split() {
-        let mut prob = Probe::new(12, 7);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 12, 7);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 1);
     }
     #[test]
     fn run_prob_split_once() {
-        let mut prob = Probe::new(0, 10);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 0, 10);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 3);
-        let mut prob = Probe::new(34, 0);
-        prob.solve();
+        let mut prob = Probe::new(&grid, 34, 0);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 2);
     }
     #[test]
     fn run_prob_split_multiple() {
-        let mut prob = Probe::new(16, 0);
-        prob.solve();
+        let grid = load_grid();
+        let mut prob = Probe::new(&grid, 16, 0);
+        prob.solve(&grid);
         assert_eq!(prob.count_trailheads(), 6);
     }
 }
@@ -1,34 +1,76 @@
-use std::fs;
+use crate::answer::{self, Answer, Overflow, Unsolvable};
+use crate::day11_part2::StonesLine;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::render::{self, Glyph, Render};
+use crate::solution::Solution;
 
-const FILE_NAME: &str = "input_day11.txt";
+const DAY: u8 = 11;
 
-pub fn main() {
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Stone>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(stones: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(blink_n_times(stones.clone(), 25)?.len().into())
+    }
+    fn part2(stones: &Self::Input) -> Result<Answer, Unsolvable> {
+        let numbers: Vec<u64> = stones.iter().map(|stone| stone.number).collect();
+        let mut line = StonesLine::from_numbers(&numbers);
+        line.blink_n_times(75)?;
+        Ok(line.count_stones()?.into())
+    }
+}
+
+impl Render for Day11 {
+    fn render(
+        stones: &Self::Input,
+        every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        let numbers: Vec<u64> = stones.iter().map(|stone| stone.number).collect();
+        let mut line = StonesLine::from_numbers(&numbers);
+        for step in 0..75 {
+            if render::is_frame(every, step) {
+                frame(&line.canvas());
+            }
+            line.blink().map_err(|overflow| overflow.to_string())?;
+        }
+        frame(&line.canvas());
+        Ok(())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let line = parse_string(&input);
-    let result = blink_n_times(line, 25);
-    println!("{}", result.len());
+    let line = parse_string(input)?;
+    let result = blink_n_times(line, 25).map(|line| line.len());
+    println!("{}", answer::show(result));
+    Ok(())
 }
 
 #[derive(PartialEq, Debug, Clone)]
-struct Stone {
+pub struct Stone {
     number: u64,
 }
 
 impl Stone {
-    fn new(number: u64) -> Stone {
+    pub fn new(number: u64) -> Stone {
         Stone { number }
     }
 
-    fn change(&self) -> Vec<Stone> {
+    fn change(&self) -> Result<Vec<Stone>, Overflow> {
         if self.number == 0 {
-            return vec![Stone::new(1)];
+            return Ok(vec![Stone::new(1)]);
         }
         if self.is_even_digits() {
-            return self.split();
+            return Ok(self.split());
         }
-        vec![Stone::new(self.number * 2024)]
+        Ok(vec![Stone::new(answer::mul(self.number, 2024, "stone")?)])
     }
     fn count_digits(&self) -> u64 {
         if self.number == 0 {
@@ -37,7 +79,7 @@ impl Stone {
         (self.number as f64).log10().floor() as u64 + 1
     }
     fn is_even_digits(&self) -> bool {
-        self.count_digits() % 2 == 0
+        self.count_digits().is_multiple_of(2)
     }
 
     fn split_number(&self) -> (u64, u64) {
@@ -52,27 +94,32 @@ impl Stone {
     }
 }
 
-fn parse_string(input: &str) -> Vec<Stone> {
+pub fn parse_string(input: &str) -> Result<Vec<Stone>, ParseError> {
+    parse_numbers(input).map(|numbers| numbers.into_iter().map(Stone::new).collect())
+}
+
+pub fn parse_numbers(input: &str) -> Result<Vec<u64>, ParseError> {
     input
         .split_whitespace()
-        .map(|str| Stone::new(str.parse().expect("not a number!")))
+        .map(|number| {
+            number
+                .parse()
+                .map_err(|_| ParseError::new(DAY, input, number, "not a number"))
+        })
         .collect()
 }
 
-fn blink(line: &[Stone]) -> Vec<Stone> {
+pub fn blink(line: &[Stone]) -> Result<Vec<Stone>, Overflow> {
     let mut result = Vec::with_capacity(line.len() * 2);
     for stone in line {
-        result.extend_from_slice(&stone.change());
+        result.extend_from_slice(&stone.change()?);
     }
     result.shrink_to_fit();
-    result
+    Ok(result)
 }
 
-fn blink_n_times(line: Vec<Stone>, n: usize) -> Vec<Stone> {
-    (0..n).enumerate().fold(line, |acc, (i, _)| {
-        println!("Blink {}/{}", i + 1, n);
-        blink(&acc)
-    })
+pub fn blink_n_times(line: Vec<Stone>, n: usize) -> Result<Vec<Stone>, Overflow> {
+    (0..n).try_fold(line, |acc, _| blink(&acc))
 }
 
 #[cfg(test)]
@@ -83,21 +130,26 @@ pub mod tests {
         let stone = Stone::new(0);
         let result = stone.change();
         let expected = vec![Stone::new(1)];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
     }
     #[test]
     fn change_stone_split() {
         let stone = Stone::new(1000);
         let result = stone.change();
         let expected = vec![Stone::new(10), Stone::new(0)];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
     }
     #[test]
     fn change_stone_multiply() {
         let stone = Stone::new(2);
         let result = stone.change();
         let expected = vec![Stone::new(4048)];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
+    }
+    #[test]
+    fn change_stone_overflow() {
+        let stone = Stone::new(10u64.pow(18));
+        assert_eq!(stone.change(), Err(Overflow { operation: "stone" }));
     }
     #[test]
     fn test_count_digits() {
@@ -140,27 +192,29 @@ pub mod tests {
 
     #[test]
     fn test_bling() {
-        let line = parse_string("125 17");
-        let result = blink(&line);
-        let expected = parse_string("253000 1 7");
+        let line = parse_string("125 17").unwrap();
+        let result = blink(&line).unwrap();
+        let expected = parse_string("253000 1 7").unwrap();
         assert_eq!(result, expected);
-        let line = parse_string("253 0 2024 14168");
-        let result = blink(&line);
-        let expected = parse_string("512072 1 20 24 28676032");
+        let line = parse_string("253 0 2024 14168").unwrap();
+        let result = blink(&line).unwrap();
+        let expected = parse_string("512072 1 20 24 28676032").unwrap();
         assert_eq!(result, expected);
-        let line = parse_string("1036288 7 2 20 24 4048 1 4048 8096 28 67 60 32");
-        let result = blink(&line);
+        let line = parse_string("1036288 7 2 20 24 4048 1 4048 8096 28 67 60 32").unwrap();
+        let result = blink(&line).unwrap();
         let expected =
-            parse_string("2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2");
+            parse_string("2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2")
+                .unwrap();
         assert_eq!(result, expected);
     }
 
     #[test]
     fn blink_6_times() {
-        let line = parse_string("125 17");
-        let result = blink_n_times(line, 6);
+        let line = parse_string("125 17").unwrap();
+        let result = blink_n_times(line, 6).unwrap();
         let expected =
-            parse_string("2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2");
+            parse_string("2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2")
+                .unwrap();
         assert_eq!(result, expected);
     }
 }
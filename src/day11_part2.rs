@@ -1,19 +1,25 @@
+use crate::answer::{self, CheckedIterator, Overflow};
+use crate::day11::parse_numbers;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::memo::Memo;
+use crate::render::{self, Glyph};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::fs;
 
-const FILE_NAME: &str = "input_day11.txt";
+// a u64 has at most 20 digits, the histogram has a column for every length
+const MAX_DIGITS: usize = 20;
+const HISTOGRAM_HEIGHT: usize = 20;
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut line = StonesLine::from(&input);
-    line.blink_n_times(75);
-    println!("{}", line.count_stones());
+    let mut line = StonesLine::from(input)?;
+    let result = line.blink_n_times(75).and_then(|_| line.count_stones());
+    println!("{}", answer::show(result));
+    Ok(())
 }
 
-struct StonesLine {
+pub struct StonesLine {
     stones: HashMap<u64, u64>,
     calculator: Calculator,
 }
@@ -26,46 +32,70 @@ impl StonesLine {
         }
     }
 
-    fn from(input: &str) -> StonesLine {
-        let stones = input
-            .split_whitespace()
-            .map(|str| str.parse().expect("not a number!"));
+    pub fn from(input: &str) -> Result<StonesLine, ParseError> {
+        Ok(StonesLine::from_numbers(&parse_numbers(input)?))
+    }
 
+    pub fn from_numbers(stones: &[u64]) -> StonesLine {
         let mut result = StonesLine::new();
         for stone in stones {
-            result.add_one(stone);
+            result.add_one(*stone);
         }
         result
     }
-    fn add(&mut self, number: u64, amount: u64) {
-        *self.stones.entry(number).or_insert(0) += amount;
+    fn add(&mut self, number: u64, amount: u64) -> Result<(), Overflow> {
+        let count = self.stones.entry(number).or_insert(0);
+        *count = answer::add(*count, amount, "stone count")?;
+        Ok(())
     }
 
+    // can't overflow, there are never more input stones than fit in memory
     fn add_one(&mut self, number: u64) {
-        self.add(number, 1)
+        *self.stones.entry(number).or_insert(0) += 1;
     }
-    fn add_multiple(&mut self, numbers: &[u64], amount: u64) {
+    fn add_multiple(&mut self, numbers: &[u64], amount: u64) -> Result<(), Overflow> {
         for number in numbers {
-            self.add(*number, amount);
+            self.add(*number, amount)?;
         }
+        Ok(())
     }
 
-    fn blink(&mut self) {
+    pub fn blink(&mut self) -> Result<(), Overflow> {
         let mut next_line = StonesLine::new();
         for (stone, amount) in &self.stones {
-            next_line.add_multiple(self.calculator.change(*stone), *amount)
+            next_line.add_multiple(self.calculator.change(*stone)?, *amount)?
         }
-        self.stones = next_line.stones
+        self.stones = next_line.stones;
+        Ok(())
     }
 
-    fn blink_n_times(&mut self, times: usize) {
+    pub fn blink_n_times(&mut self, times: usize) -> Result<(), Overflow> {
         for _ in 0..times {
-            self.blink()
+            self.blink()?
         }
+        Ok(())
     }
 
-    fn count_stones(&self) -> u64 {
-        self.stones.iter().fold(0, |acc, (_, amount)| acc + amount)
+    pub fn count_stones(&self) -> Result<u64, Overflow> {
+        self.stones.values().copied().checked_sum("stone count")
+    }
+
+    // how the stones split by number of digits, column i holds the stones with i + 1 digits
+    // and its bar is as high as their share of all stones
+    pub fn canvas(&self) -> Grid<Glyph> {
+        let mut counts = [0u128; MAX_DIGITS];
+        for (&number, &amount) in &self.stones {
+            counts[Calculator::count_digits(number) as usize - 1] += amount as u128;
+        }
+        let total: u128 = counts.iter().sum();
+        let mut canvas = Grid::filled(MAX_DIGITS, HISTOGRAM_HEIGHT, Glyph::new('.'));
+        for (column, &count) in counts.iter().enumerate() {
+            let bar = (count * HISTOGRAM_HEIGHT as u128).div_ceil(total.max(1)) as usize;
+            for y in HISTOGRAM_HEIGHT - bar..HISTOGRAM_HEIGHT {
+                canvas[(column, y)] = Glyph::new('#').fg(render::palette(column));
+            }
+        }
+        canvas
     }
 }
 impl Debug for StonesLine {
@@ -81,29 +111,28 @@ impl PartialEq<Self> for StonesLine {
 
 #[derive(Clone)]
 struct Calculator {
-    cache: HashMap<u64, Vec<u64>>,
+    cache: Memo<u64, Result<Vec<u64>, Overflow>>,
 }
 impl Calculator {
     fn new() -> Calculator {
-        Calculator {
-            cache: HashMap::new(),
-        }
+        Calculator { cache: Memo::new() }
     }
 
-    fn change(&mut self, number: u64) -> &[u64] {
+    fn change(&mut self, number: u64) -> Result<&[u64], Overflow> {
         self.cache
-            .entry(number)
-            .or_insert(Calculator::calculate_change(number))
+            .get_or_insert_with(number, |&number| Calculator::calculate_change(number))
+            .as_deref()
+            .map_err(|&overflow| overflow)
     }
 
-    fn calculate_change(number: u64) -> Vec<u64> {
+    fn calculate_change(number: u64) -> Result<Vec<u64>, Overflow> {
         if number == 0 {
-            return vec![1];
+            return Ok(vec![1]);
         }
         if Calculator::is_even_digits(number) {
-            return Calculator::split(number);
+            return Ok(Calculator::split(number));
         }
-        vec![number * 2024]
+        Ok(vec![answer::mul(number, 2024, "stone")?])
     }
     fn count_digits(number: u64) -> u64 {
         if number == 0 {
@@ -135,48 +164,78 @@ pub mod tests {
         let stone = 0;
         let result = Calculator::calculate_change(stone);
         let expected = vec![1];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
     }
     #[test]
     fn change_stone_split() {
         let stone = 1000;
         let result = Calculator::calculate_change(stone);
         let expected = vec![10, 0];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
     }
     #[test]
     fn change_stone_multiply() {
         let stone = 2;
         let result = Calculator::calculate_change(stone);
         let expected = vec![4048];
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
+    }
+    #[test]
+    fn overflow() {
+        let mut line = StonesLine::from("1000000000000000000").unwrap();
+        assert_eq!(line.blink(), Err(Overflow { operation: "stone" }));
+        let mut line = StonesLine::from_numbers(&[1]);
+        line.stones.insert(0, u64::MAX);
+        assert!(line.count_stones().is_err());
     }
 
     #[test]
     fn test_bling() {
-        let mut line = StonesLine::from("125 17");
-        line.blink();
-        let expected = StonesLine::from("253000 1 7");
+        let mut line = StonesLine::from("125 17").unwrap();
+        line.blink().unwrap();
+        let expected = StonesLine::from("253000 1 7").unwrap();
         assert_eq!(line, expected);
-        let mut line = StonesLine::from("253 0 2024 14168");
-        line.blink();
-        let expected = StonesLine::from("512072 1 20 24 28676032");
+        let mut line = StonesLine::from("253 0 2024 14168").unwrap();
+        line.blink().unwrap();
+        let expected = StonesLine::from("512072 1 20 24 28676032").unwrap();
         assert_eq!(line, expected);
-        let mut line = StonesLine::from("1036288 7 2 20 24 4048 1 4048 8096 28 67 60 32");
-        line.blink();
+        let mut line = StonesLine::from("1036288 7 2 20 24 4048 1 4048 8096 28 67 60 32").unwrap();
+        line.blink().unwrap();
         let expected = StonesLine::from(
             "2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2",
-        );
+        )
+        .unwrap();
         assert_eq!(line, expected);
     }
 
+    #[test]
+    fn reuse_changes() {
+        let mut line = StonesLine::from("0").unwrap();
+        line.blink_n_times(5).unwrap();
+        let stats = line.calculator.cache.stats();
+        assert_eq!((stats.hits, stats.misses), (1, 7));
+        assert_eq!(line.count_stones(), Ok(4));
+    }
+
+    #[test]
+    fn histogram() {
+        let line = StonesLine::from("1 2 3 40").unwrap();
+        let canvas = line.canvas();
+        assert_eq!((canvas.width(), canvas.height()), (20, 20));
+        let bar = |x| (0..20).filter(|&y| canvas[(x, y)].symbol == '#').count();
+        assert_eq!((bar(0), bar(1), bar(2)), (15, 5, 0));
+        assert_eq!(canvas[(0, 19)].symbol, '#');
+        assert_eq!(canvas[(0, 4)].symbol, '.');
+    }
+
     #[test]
     fn blink_6_times() {
-        let mut line = StonesLine::from("125 17");
-        line.blink_n_times(6);
+        let mut line = StonesLine::from("125 17").unwrap();
+        line.blink_n_times(6).unwrap();
         let expected = StonesLine::from(
             "2097446912 14168 4048 2 0 2 4 40 48 2024 40 48 80 96 2 8 6 7 6 0 3 2",
-        );
+        )
+        .unwrap();
         assert_eq!(line, expected);
     }
 }
@@ -1,40 +1,75 @@
+use crate::answer::{self, Answer, Overflow, Unsolvable};
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::pathfinding;
+use crate::position::{Direction, Position};
+use crate::render::{self, Glyph, Render};
+use crate::solution::Solution;
 use itertools::Itertools;
 use std::cmp::PartialEq;
-use std::collections::{HashSet, VecDeque};
-use std::fs;
+use std::collections::HashSet;
 
-const FILE_NAME: &str = "input_day12.txt";
+const DAY: u8 = 12;
 
-pub fn main() {
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Region>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(regions: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(calculate_fence_cost(regions)?.into())
+    }
+    fn part2(regions: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(calculate_bulk_fence_cost(regions)?.into())
+    }
+}
+
+impl Render for Day12 {
+    fn render(
+        regions: &Self::Input,
+        _every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        frame(&regions_canvas(regions));
+        Ok(())
+    }
+}
+
+// every crop in its own colour, the regions only know their cells so the size comes from them
+fn regions_canvas(regions: &[Region]) -> Grid<Glyph> {
+    let cells = || regions.iter().flat_map(|region| region.cells.iter());
+    let width = cells().map(|cell| cell.x + 1).max().unwrap_or(0);
+    let height = cells().map(|cell| cell.y + 1).max().unwrap_or(0);
+    let mut canvas = Grid::filled(width, height, Glyph::new(' '));
+    for cell in cells() {
+        canvas[(cell.x, cell.y)] = Glyph::new(cell.crop).fg(render::palette(cell.crop as usize));
+    }
+    canvas
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let regions = parse_string(&input);
+    let regions = parse_string(input)?;
     let part1_cost = calculate_fence_cost(&regions);
-    println!("fence cost = {}", part1_cost);
+    println!("fence cost = {}", answer::show(part1_cost));
     let part2_cost = calculate_bulk_fence_cost(&regions);
-    println!("bulk fence cost = {}", part2_cost);
-}
-
-fn calculate_bulk_fence_cost(regions: &[Region]) -> u32 {
-    regions
-        .iter()
-        .map(|region: &Region| region.bulk_fence_cost())
-        .sum()
+    println!("bulk fence cost = {}", answer::show(part2_cost));
+    Ok(())
 }
 
-fn calculate_fence_cost(regions: &[Region]) -> u32 {
-    regions
-        .iter()
-        .map(|region: &Region| region.fence_cost())
-        .sum()
+pub fn calculate_bulk_fence_cost(regions: &[Region]) -> Result<u32, Overflow> {
+    regions.iter().try_fold(0, |total, region: &Region| {
+        answer::add(total, region.bulk_fence_cost()?, "fence cost")
+    })
 }
 
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+pub fn calculate_fence_cost(regions: &[Region]) -> Result<u32, Overflow> {
+    regions.iter().try_fold(0, |total, region: &Region| {
+        answer::add(total, region.fence_cost()?, "fence cost")
+    })
 }
 
 #[derive(Hash, Eq, PartialEq, Clone, Copy)]
@@ -50,7 +85,7 @@ impl Cell {
 }
 
 #[derive(Eq, PartialEq)]
-struct Region {
+pub struct Region {
     crop: char,
     cells: HashSet<Cell>,
 }
@@ -63,28 +98,21 @@ impl Region {
         }
     }
 
-    fn is_edge(&self, cell: &Cell, direction: &Direction) -> bool {
-        let next_xy = match direction {
-            Direction::Up => (Some(cell.x), cell.y.checked_sub(1)),
-            Direction::Down => (Some(cell.x), Some(cell.y + 1)),
-            Direction::Left => (cell.x.checked_sub(1), Some(cell.y)),
-            Direction::Right => (Some(cell.x + 1), Some(cell.y)),
-        };
-        if let (Some(x), Some(y)) = next_xy {
-            !self.cells.contains(&Cell {
+    fn is_edge(&self, cell: &Cell, direction: Direction) -> bool {
+        match Position::new(cell.x, cell.y) + direction {
+            Some(Position { x, y }) => !self.cells.contains(&Cell {
                 x,
                 y,
                 crop: cell.crop,
-            })
-        } else {
-            true
+            }),
+            None => true,
         }
     }
 
-    fn area(&self) -> u32 {
+    pub fn area(&self) -> u32 {
         self.cells.len() as u32
     }
-    fn perimeter(&self) -> u32 {
+    pub fn perimeter(&self) -> u32 {
         let cells_diameter = self.cells.len() * 4;
         let edges_to_substruct = self
             .iter()
@@ -93,12 +121,12 @@ impl Region {
             .count();
         (cells_diameter - edges_to_substruct) as u32
     }
-    fn sides(&self) -> u32 {
+    pub fn sides(&self) -> u32 {
         let mut result = 0;
         for direction in [Direction::Up, Direction::Down] {
             result += self
                 .iter()
-                .filter(|cell: &&Cell| self.is_edge(cell, &direction))
+                .filter(|cell: &&Cell| self.is_edge(cell, direction))
                 .map(|cell: &Cell| (cell.x, cell.y))
                 .sorted_by(|(x1, y1), (x2, y2)| y1.cmp(y2).then(x1.cmp(x2)))
                 .chunk_by(|(_x, y)| *y)
@@ -113,7 +141,7 @@ impl Region {
         for direction in [Direction::Right, Direction::Left] {
             result += self
                 .iter()
-                .filter(|cell: &&Cell| self.is_edge(cell, &direction))
+                .filter(|cell: &&Cell| self.is_edge(cell, direction))
                 .map(|cell: &Cell| (cell.x, cell.y))
                 .sorted_by(|(x1, y1), (x2, y2)| x1.cmp(x2).then(y1.cmp(y2)))
                 .chunk_by(|(x, _y)| *x)
@@ -135,96 +163,48 @@ impl Region {
         self.cells.iter()
     }
 
-    fn bulk_fence_cost(&self) -> u32 {
-        self.area() * self.sides()
+    pub fn bulk_fence_cost(&self) -> Result<u32, Overflow> {
+        answer::mul(self.area(), self.sides(), "fence cost")
     }
 
-    fn fence_cost(&self) -> u32 {
-        self.area() * self.perimeter()
+    pub fn fence_cost(&self) -> Result<u32, Overflow> {
+        answer::mul(self.area(), self.perimeter(), "fence cost")
     }
 }
 
-struct Grid {
-    data: Vec<Vec<Cell>>,
-    shape: (usize, usize),
+fn bfs(grid: &Grid<char>, start: Point) -> HashSet<Point> {
+    let crop = grid[start];
+    let same_crop = |&point: &Point| grid.neighbours4(point).filter(|&n| grid[n] == crop);
+    pathfinding::bfs(start, same_crop)
+        .reached()
+        .map(|(&point, _)| point)
+        .collect()
 }
 
-impl Grid {
-    fn from(input: &str) -> Grid {
-        let data: Vec<_> = input
-            .trim()
-            .lines()
-            .enumerate()
-            .map(|(y, line)| {
-                line.trim()
-                    .chars()
-                    .enumerate()
-                    .map(move |(x, crop)| Cell { x, y, crop })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-        let shape = (data[0].len(), data.len());
-        Grid { data, shape }
-    }
-    fn iter(&self) -> impl Iterator<Item = &Cell> {
-        self.data.iter().flatten()
-    }
-    fn get_cell(&self, x: usize, y: usize) -> Option<&Cell> {
-        if x >= self.shape.0 || y >= self.shape.1 {
-            None
-        } else {
-            Some(&self.data[y][x])
+fn calculate_regions(grid: &Grid<char>) -> Vec<Region> {
+    let mut result = Vec::new();
+    let mut visited = Grid::filled(grid.width(), grid.height(), false);
+    for (point, &crop) in grid.iter() {
+        if visited[point] {
+            continue;
         }
-    }
-    fn get_identical_neighbors(&self, cell: &Cell) -> HashSet<&Cell> {
-        let candidates = [
-            (cell.x, cell.y + 1),
-            (cell.x + 1, cell.y),
-            (cell.x.saturating_sub(1), cell.y),
-            (cell.x, cell.y.saturating_sub(1)),
-        ];
-        candidates
-            .into_iter()
-            .filter_map(|(x, y)| self.get_cell(x, y))
-            .filter(|&c| c != cell)
-            .filter(|&c| c.crop == cell.crop)
-            .collect()
-    }
-
-    fn bfs<'a>(&'a self, start: &'a Cell) -> HashSet<&'a Cell> {
-        let mut visited: HashSet<&Cell> = HashSet::from([start]);
-        let mut queue = VecDeque::from([start]);
-
-        while let Some(cell) = queue.pop_front() {
-            for neighbor in self.get_identical_neighbors(cell) {
-                if visited.insert(neighbor) {
-                    queue.push_back(neighbor)
-                }
-            }
+        let cells_in_region = bfs(grid, point);
+        for &cell in &cells_in_region {
+            visited[cell] = true;
         }
-        visited
+        result.push(Region::from(
+            cells_in_region
+                .into_iter()
+                .map(|(x, y)| Cell { x, y, crop })
+                .collect(),
+        ))
     }
 
-    fn calculate_regions(&mut self) -> Vec<Region> {
-        let mut result = Vec::new();
-        let mut visited: HashSet<&Cell> = HashSet::new();
-        for cell in self.iter() {
-            if visited.contains(&cell) {
-                continue;
-            } else {
-                let cells_in_region = self.bfs(cell);
-                visited.extend(&cells_in_region);
-                result.push(Region::from(cells_in_region.into_iter().cloned().collect()))
-            }
-        }
-
-        result
-    }
+    result
 }
 
-fn parse_string(input: &str) -> Vec<Region> {
-    let mut grid = Grid::from(input);
-    grid.calculate_regions()
+pub fn parse_string(input: &str) -> Result<Vec<Region>, ParseError> {
+    Ok(calculate_regions(&Grid::chars(DAY, input)?))
 }
 
 #[cfg(test)]
@@ -238,22 +218,11 @@ pub mod tests {
             BBCD
             BBCC
             EEEC";
-        let grid = Grid::from(input);
-        let cell = grid.get_cell(0, 0).unwrap();
-        let bfs_result = grid.bfs(cell);
-        assert_eq!(bfs_result.len(), 4);
-
-        let cell = grid.get_cell(1, 1).unwrap();
-        let bfs_result = grid.bfs(cell);
-        assert_eq!(bfs_result.len(), 4);
-
-        let cell = grid.get_cell(3, 1).unwrap();
-        let bfs_result = grid.bfs(cell);
-        assert_eq!(bfs_result.len(), 1);
-
-        let cell = grid.get_cell(1, 3).unwrap();
-        let bfs_result = grid.bfs(cell);
-        assert_eq!(bfs_result.len(), 3);
+        let grid = Grid::chars(DAY, input).unwrap();
+        assert_eq!(bfs(&grid, (0, 0)).len(), 4);
+        assert_eq!(bfs(&grid, (1, 1)).len(), 4);
+        assert_eq!(bfs(&grid, (3, 1)).len(), 1);
+        assert_eq!(bfs(&grid, (1, 3)).len(), 3);
     }
 
     #[test]
@@ -263,7 +232,7 @@ pub mod tests {
             BBCD
             BBCC
             EEEC";
-        let result = parse_string(input);
+        let result = parse_string(input).unwrap();
         assert_eq!(result.len(), 5);
         let input = r"
             OOOOO
@@ -271,7 +240,7 @@ pub mod tests {
             OOOOO
             OXOXO
             OOOOO";
-        let result = parse_string(input);
+        let result = parse_string(input).unwrap();
         assert_eq!(result.len(), 5);
         let input = r"
             RRRRIICCFF
@@ -284,7 +253,7 @@ pub mod tests {
             MIIIIIJJEE
             MIIISIJEEE
             MMMISSJEEE";
-        let result = parse_string(input);
+        let result = parse_string(input).unwrap();
         assert_eq!(result.len(), 11);
     }
 
@@ -301,9 +270,9 @@ pub mod tests {
             MIIIIIJJEE
             MIIISIJEEE
             MMMISSJEEE";
-        let regions = parse_string(input);
+        let regions = parse_string(input).unwrap();
         let cost = calculate_fence_cost(&regions);
-        assert_eq!(cost, 1930);
+        assert_eq!(cost, Ok(1930));
     }
 
     #[test]
@@ -313,10 +282,10 @@ pub mod tests {
             BBCD
             BBCC
             EEEC";
-        let regions = parse_string(input);
+        let regions = parse_string(input).unwrap();
         let cost = calculate_bulk_fence_cost(&regions);
 
-        assert_eq!(cost, 80);
+        assert_eq!(cost, Ok(80));
         let input = r"
             RRRRIICCFF
             RRRRIICCCF
@@ -328,9 +297,18 @@ pub mod tests {
             MIIIIIJJEE
             MIIISIJEEE
             MMMISSJEEE";
-        let regions = parse_string(input);
+        let regions = parse_string(input).unwrap();
         let cost = calculate_bulk_fence_cost(&regions);
-        assert_eq!(cost, 1206);
+        assert_eq!(cost, Ok(1206));
+    }
+
+    #[test]
+    fn render_regions() {
+        let regions = parse_string("AAB\nCAB").unwrap();
+        let canvas = regions_canvas(&regions);
+        assert_eq!(render::draw(&canvas, false), "AAB\nCAB\n");
+        assert_eq!(canvas[(1, 1)], canvas[(0, 0)]);
+        assert_ne!(canvas[(2, 0)].foreground, canvas[(0, 0)].foreground);
     }
 
     #[test]
@@ -347,14 +325,14 @@ pub mod tests {
         let region = Region { crop, cells };
         let cell = Cell { x: 1, y: 1, crop };
         let direction = Direction::Down;
-        assert!(region.is_edge(&cell, &direction));
+        assert!(region.is_edge(&cell, direction));
         let direction = Direction::Up;
-        assert!(!region.is_edge(&cell, &direction));
+        assert!(!region.is_edge(&cell, direction));
         let cell = Cell { x: 1, y: 0, crop };
         let direction = Direction::Down;
-        assert!(!region.is_edge(&cell, &direction));
+        assert!(!region.is_edge(&cell, direction));
         let direction = Direction::Up;
-        assert!(region.is_edge(&cell, &direction));
+        assert!(region.is_edge(&cell, direction));
     }
 
     #[test]
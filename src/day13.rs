@@ -1,14 +1,57 @@
-use itertools::Itertools;
-use nalgebra::{ComplexField, Matrix2, Vector2};
-use std::fs;
+use crate::answer::{self, Answer, CheckedIterator, Overflow, Unsolvable};
+use crate::error::ParseError;
+use crate::scan::Pattern;
+use crate::solution::Solution;
+use crate::vector::Vector2;
 
-const FILE_NAME: &str = "input_day13.txt";
+const DAY: u8 = 13;
+const A_TOKENS: i64 = 3;
+const B_TOKENS: i64 = 1;
+pub const MAX_PRESSES: i64 = 100;
+pub const PRIZE_OFFSET: i64 = 10_000_000_000_000;
 
-pub fn main() {
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<ClawMachine>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(machines: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(total_tokens(machines, 0, Some(MAX_PRESSES))?.into())
+    }
+    fn part2(machines: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(total_tokens(machines, PRIZE_OFFSET, None)?.into())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let parsed = parse_string(&input);
+    let parsed = parse_string(input)?;
+    println!("{}", answer::show(Day13::part1(&parsed)));
+    Ok(())
+}
+
+fn total_tokens(
+    machines: &[ClawMachine],
+    offset: i64,
+    max_presses: Option<i64>,
+) -> Result<i64, Overflow> {
+    machines
+        .iter()
+        .map(|machine| machine.cheapest_win(offset, max_presses))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .checked_sum("tokens")
+}
+
+fn cross(a: Vector2<i64>, b: Vector2<i64>) -> Result<i64, Overflow> {
+    const OPERATION: &str = "cross product";
+    let first = answer::mul(a.x, b.y, OPERATION)?;
+    let second = answer::mul(a.y, b.x, OPERATION)?;
+    answer::sub(first, second, OPERATION)
 }
 
 #[derive(PartialEq, Debug)]
@@ -19,88 +62,219 @@ enum NumberOfSolutions {
 }
 
 impl NumberOfSolutions {
-    fn calculate(matrix: &Matrix2<f32>, rhs: &Vector2<f32>) -> NumberOfSolutions {
-        let option_solution = matrix.clone().lu().solve(rhs);
-        match option_solution {
-            None => {
-                let cramer_matrix = Matrix2::from_columns(&[matrix.column(0), rhs.column(0)]);
-                if cramer_matrix.determinant().abs() < 1e-7 {
-                    NumberOfSolutions::Infinity
-                } else {
-                    NumberOfSolutions::None
-                }
-            }
-            Some(solution) => {
-                if ClawMachine::is_vector_natural(solution) {
-                    NumberOfSolutions::One
-                } else {
-                    NumberOfSolutions::None
-                }
+    // solutions of a * `a` + b * `b` = `target` in natural numbers a and b, or all three lie
+    // on one line and there may be many
+    fn calculate(
+        a: Vector2<i64>,
+        b: Vector2<i64>,
+        target: Vector2<i64>,
+    ) -> Result<NumberOfSolutions, Overflow> {
+        if cross(a, b)? == 0 {
+            return Ok(if cross(a, target)? == 0 && cross(b, target)? == 0 {
+                NumberOfSolutions::Infinity
+            } else {
+                NumberOfSolutions::None
+            });
+        }
+        Ok(match ClawMachine::cramer(a, b, target)? {
+            Some(_) => NumberOfSolutions::One,
+            None => NumberOfSolutions::None,
+        })
+    }
+}
+
+// the cheapest natural a and b up to `limit` with a * `a` + b * `b` = `target` when all three
+// lie on one line, which leaves a single equation along that line
+fn cheapest_on_line(
+    a: Vector2<i64>,
+    b: Vector2<i64>,
+    target: Vector2<i64>,
+    limit: i64,
+) -> Option<(i64, i64)> {
+    let direction = if a == Vector2::default() { b } else { a }.reduced();
+    if direction == Vector2::default() {
+        return (target == direction).then_some((0, 0));
+    }
+    let along = |v: Vector2<i64>| {
+        i128::from(match direction.x {
+            0 => v.y / direction.y,
+            x => v.x / x,
+        })
+    };
+    let (a_length, b_length) = (along(a), along(b));
+    let (gcd, x, y) = extended_gcd(a_length, b_length);
+    let target = along(target);
+    if target % gcd != 0 {
+        return None;
+    }
+    // every solution is (a + t * a_step, b + t * b_step) for some integer t
+    let (a, b) = (x * (target / gcd), y * (target / gcd));
+    let (a_step, b_step) = (b_length / gcd, -a_length / gcd);
+    let limit = i128::from(limit);
+    let (mut low, mut high) = (i128::MIN, i128::MAX);
+    for (start, step) in [(a, a_step), (b, b_step)] {
+        if step == 0 {
+            if !(0..=limit).contains(&start) {
+                return None;
             }
+            continue;
         }
+        // 0 <= start + t * step <= limit
+        let (from, to) = if step > 0 {
+            (-start, limit - start)
+        } else {
+            (limit - start, -start)
+        };
+        low = low.max(-floor_div(-from, step));
+        high = high.min(floor_div(to, step));
+    }
+    if low > high {
+        return None;
     }
+    // the tokens grow or shrink along t, so one of the ends is the cheapest
+    let presses = |t: i128| (a + t * a_step, b + t * b_step);
+    let tokens = |(a, b): (i128, i128)| a * i128::from(A_TOKENS) + b * i128::from(B_TOKENS);
+    let (a, b) = [presses(low), presses(high)]
+        .into_iter()
+        .min_by_key(|&presses| tokens(presses))?;
+    // both are within the limit
+    Some((a as i64, b as i64))
 }
 
-struct ClawMachine {
-    movement_matrix: Matrix2<f32>,
-    target: Vector2<f32>,
-    number_of_solutions: NumberOfSolutions,
+// the gcd of `a` and `b` with x and y such that a * x + b * y = gcd
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (gcd, x, y) = extended_gcd(b, a % b);
+    (gcd, y, x - a / b * y)
 }
 
-impl ClawMachine {
-    fn new(a_movement: (f32, f32), b_movement: (f32, f32), target: (f32, f32)) -> ClawMachine {
-        let movement_matrix =
-            Matrix2::from_row_slice(&[a_movement.0, b_movement.0, a_movement.1, b_movement.1]);
-        let target = Vector2::from_row_slice(&[target.0, target.1]);
+fn floor_div(numerator: i128, denominator: i128) -> i128 {
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+#[derive(Debug)]
+pub struct ClawMachine {
+    a_movement: Vector2<i64>,
+    b_movement: Vector2<i64>,
+    target: Vector2<i64>,
+}
 
+impl ClawMachine {
+    fn new(
+        a_movement: Vector2<i64>,
+        b_movement: Vector2<i64>,
+        target: Vector2<i64>,
+    ) -> ClawMachine {
         ClawMachine {
-            movement_matrix,
+            a_movement,
+            b_movement,
             target,
-            number_of_solutions: NumberOfSolutions::calculate(&movement_matrix, &target),
         }
     }
 
-    fn from(input: &str) -> ClawMachine {
+    #[cfg(test)]
+    fn number_of_solutions(&self) -> Result<NumberOfSolutions, Overflow> {
+        NumberOfSolutions::calculate(self.a_movement, self.b_movement, self.target)
+    }
+
+    pub fn from(input: &str) -> Result<ClawMachine, ParseError> {
         let mut input_lines = input.trim().lines();
-        let a_movement = Self::parse_line(input_lines.next(), "Button A: X+{d}, Y+{d}");
-        let b_movement = Self::parse_line(input_lines.next(), "Button B: X+{d}, Y+{d}");
-        let target = Self::parse_line(input_lines.next(), "Prize: X={d}, Y={d}");
+        let mut parse_next = |pattern| {
+            let line = input_lines.next().ok_or_else(|| {
+                ParseError::new(DAY, input, &input[input.len()..], "missing line")
+            })?;
+            let (x, y) = Pattern::new(DAY, pattern)
+                .scan(line)
+                .map_err(|error| error.within(input, line))?;
+            Ok(Vector2::new(x, y))
+        };
+        let a_movement = parse_next("Button A: X{int}, Y{int}")?;
+        let b_movement = parse_next("Button B: X{int}, Y{int}")?;
+        let target = parse_next("Prize: X={int}, Y={int}")?;
 
-        ClawMachine::new(a_movement, b_movement, target)
+        Ok(ClawMachine::new(a_movement, b_movement, target))
     }
 
-    fn parse_line(line: Option<&str>, pattern: &str) -> (f32, f32) {
-        let s = line.expect("missing line");
-        let mut p = pattern.split("{d}");
-        let (pre, mid) = (p.next().unwrap(), p.next().unwrap());
+    pub fn cheapest_win(
+        &self,
+        offset: i64,
+        max_presses: Option<i64>,
+    ) -> Result<Option<i64>, Overflow> {
+        let target = Vector2::new(
+            answer::add(self.target.x, offset, "prize offset")?,
+            answer::add(self.target.y, offset, "prize offset")?,
+        );
+        let limit = max_presses.unwrap_or(i64::MAX);
+        let Some((a, b)) = Self::solve(self.a_movement, self.b_movement, target, limit)? else {
+            return Ok(None);
+        };
+        let tokens = answer::add(
+            answer::mul(a, A_TOKENS, "tokens")?,
+            answer::mul(b, B_TOKENS, "tokens")?,
+            "tokens",
+        )?;
+        Ok(Some(tokens))
+    }
 
-        let (n1, n2) = s
-            .trim()
-            .strip_prefix(pre)
-            .and_then(|s| s.split_once(mid))
-            .expect("pattern mismatch");
-        (
-            n1.parse().expect("invalid f32"),
-            n2.parse().expect("invalid f32"),
-        )
+    // the cheapest presses of both buttons up to `limit` that reach `target`
+    fn solve(
+        a: Vector2<i64>,
+        b: Vector2<i64>,
+        target: Vector2<i64>,
+        limit: i64,
+    ) -> Result<Option<(i64, i64)>, Overflow> {
+        Ok(match NumberOfSolutions::calculate(a, b, target)? {
+            NumberOfSolutions::None => None,
+            NumberOfSolutions::One => {
+                Self::cramer(a, b, target)?.filter(|&(a, b)| a <= limit && b <= limit)
+            }
+            NumberOfSolutions::Infinity => cheapest_on_line(a, b, target, limit),
+        })
     }
 
-    fn is_vector_natural(v: Vector2<f32>) -> bool {
-        v.iter()
-            .all(|element| element.fract() == 0.0 && element >= &0.0)
+    // the unique presses of both buttons reaching `target` by Cramer's rule, if they're natural
+    fn cramer(
+        a: Vector2<i64>,
+        b: Vector2<i64>,
+        target: Vector2<i64>,
+    ) -> Result<Option<(i64, i64)>, Overflow> {
+        let determinant = cross(a, b)?;
+        let a_numerator = cross(target, b)?;
+        let b_numerator = cross(a, target)?;
+        // only i64::MIN / -1 doesn't fit
+        let divide = |numerator: i64| {
+            let quotient = numerator.checked_div(determinant);
+            quotient.ok_or(Overflow {
+                operation: "button presses",
+            })
+        };
+        let (a, b) = (divide(a_numerator)?, divide(b_numerator)?);
+        if a * determinant != a_numerator || b * determinant != b_numerator {
+            return Ok(None);
+        }
+        Ok((a >= 0 && b >= 0).then_some((a, b)))
     }
 }
 
-fn parse_string(input: &str) -> Vec<ClawMachine> {
+pub fn parse_string(input: &str) -> Result<Vec<ClawMachine>, ParseError> {
     input
-        .split("\r\n\r\n")
-        .map(|s| ClawMachine::from(s))
+        .split("\n\n")
+        .map(|block| ClawMachine::from(block).map_err(|error| error.within(input, block)))
         .collect()
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::solution::solve;
+    use std::fs;
     #[test]
     fn claw_machine_from_str() {
         let s = r"
@@ -108,21 +282,17 @@ pub mod tests {
         Button B: X+22, Y+67
         Prize: X=8400, Y=5400";
 
-        let machine = ClawMachine::from(s);
+        let machine = ClawMachine::from(s).unwrap();
 
-        assert_eq!(machine.movement_matrix[(0, 0)], 94.0);
-        assert_eq!(machine.movement_matrix[(0, 1)], 22.0);
-        assert_eq!(machine.movement_matrix[(1, 0)], 34.0);
-        assert_eq!(machine.movement_matrix[(1, 1)], 67.0);
-        assert_eq!(machine.target[0], 8400.0);
-        assert_eq!(machine.target[1], 5400.0);
+        assert_eq!(machine.a_movement, Vector2::new(94, 34));
+        assert_eq!(machine.b_movement, Vector2::new(22, 67));
+        assert_eq!(machine.target, Vector2::new(8400, 5400));
     }
 
     #[test]
     fn parse_string_test() {
-        let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-        let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-        let parsed = parse_string(&input);
+        let input = fs::read_to_string("artifacts/input_files/input_day13.txt").unwrap();
+        let parsed = parse_string(&input).unwrap();
         assert_eq!(parsed.len(), 320);
     }
     #[test]
@@ -132,9 +302,9 @@ pub mod tests {
         Button B: X+22, Y+67
         Prize: X=8400, Y=5400";
 
-        let machine = ClawMachine::from(s);
+        let machine = ClawMachine::from(s).unwrap();
 
-        assert_eq!(machine.number_of_solutions, NumberOfSolutions::One);
+        assert_eq!(machine.number_of_solutions(), Ok(NumberOfSolutions::One));
     }
     #[test]
     fn no_solution() {
@@ -143,25 +313,144 @@ pub mod tests {
         Button B: X+67, Y+21
         Prize: X=12748, Y=12176";
 
-        let machine = ClawMachine::from(s);
-        assert_eq!(machine.number_of_solutions, NumberOfSolutions::None);
+        let machine = ClawMachine::from(s).unwrap();
+        assert_eq!(machine.number_of_solutions(), Ok(NumberOfSolutions::None));
         let s = r"
         Button A: X+26, Y+52
         Button B: X+67, Y+132
         Prize: X=12748, Y=12176";
 
-        let machine = ClawMachine::from(s);
-        assert_eq!(machine.number_of_solutions, NumberOfSolutions::None);
+        let machine = ClawMachine::from(s).unwrap();
+        assert_eq!(machine.number_of_solutions(), Ok(NumberOfSolutions::None));
+    }
+    #[test]
+    fn cheapest_win() {
+        let s = r"
+        Button A: X+94, Y+34
+        Button B: X+22, Y+67
+        Prize: X=8400, Y=5400";
+        let machine = ClawMachine::from(s).unwrap();
+        assert_eq!(machine.cheapest_win(0, Some(MAX_PRESSES)), Ok(Some(280)));
+        assert_eq!(machine.cheapest_win(PRIZE_OFFSET, None), Ok(None));
+
+        let s = r"
+        Button A: X+26, Y+66
+        Button B: X+67, Y+21
+        Prize: X=12748, Y=12176";
+        let machine = ClawMachine::from(s).unwrap();
+        assert_eq!(machine.cheapest_win(0, Some(MAX_PRESSES)), Ok(None));
+        assert!(machine.cheapest_win(PRIZE_OFFSET, None).unwrap().is_some());
     }
+
     #[test]
     fn infinity_solutions() {
+        let s = r"
+        Button A: X+26, Y+52
+        Button B: X+13, Y+26
+        Prize: X=3198, Y=6396";
+
+        let machine = ClawMachine::from(s).unwrap();
+
+        assert_eq!(
+            machine.number_of_solutions(),
+            Ok(NumberOfSolutions::Infinity)
+        );
+        // 246 presses of B are cheapest, with at most 100 each A has to make up for the rest
+        assert_eq!(machine.cheapest_win(0, None), Ok(Some(246)));
+        assert_eq!(machine.cheapest_win(0, Some(MAX_PRESSES)), Ok(Some(319)));
+        assert_eq!(machine.cheapest_win(1, None), Ok(None));
+        // the prize is on the line of A, but B isn't parallel to it so only 123 A presses work
         let s = r"
         Button A: X+26, Y+52
         Button B: X+67, Y+132
         Prize: X=3198, Y=6396";
 
-        let machine = ClawMachine::from(s);
+        let machine = ClawMachine::from(s).unwrap();
+
+        assert_eq!(machine.number_of_solutions(), Ok(NumberOfSolutions::One));
+        assert_eq!(machine.cheapest_win(0, None), Ok(Some(369)));
+    }
 
-        assert_eq!(machine.number_of_solutions, NumberOfSolutions::Infinity)
+    #[test]
+    fn on_a_line() {
+        let (a, b) = (Vector2::new(2, 2), Vector2::new(1, 1));
+        assert_eq!(
+            cheapest_on_line(a, b, Vector2::new(10, 10), 100),
+            Some((0, 10))
+        );
+        assert_eq!(
+            cheapest_on_line(a, b, Vector2::new(10, 10), 5),
+            Some((3, 4))
+        );
+        assert_eq!(cheapest_on_line(a, b, Vector2::new(10, 10), 3), None);
+        let (a, b) = (Vector2::new(0, 3), Vector2::new(0, -2));
+        assert_eq!(
+            cheapest_on_line(a, b, Vector2::new(0, 1), 100),
+            Some((1, 1))
+        );
+        assert_eq!(
+            cheapest_on_line(a, b, Vector2::new(0, -4), 100),
+            Some((0, 2))
+        );
+        let zero = Vector2::default();
+        assert_eq!(
+            cheapest_on_line(zero, b, Vector2::new(0, -6), 100),
+            Some((0, 3))
+        );
+        assert_eq!(cheapest_on_line(a, zero, Vector2::new(0, 7), 100), None);
+        assert_eq!(cheapest_on_line(zero, zero, zero, 100), Some((0, 0)));
+        assert_eq!(cheapest_on_line(zero, zero, a, 100), None);
+    }
+
+    #[test]
+    fn overflow() {
+        let s = r"
+        Button A: X+3037000500, Y+1
+        Button B: X+1, Y+3037000500
+        Prize: X=8400, Y=5400";
+        let machine = ClawMachine::from(s).unwrap();
+        let overflow = Overflow {
+            operation: "cross product",
+        };
+        assert_eq!(machine.cheapest_win(0, None), Err(overflow));
+        let s = r"
+        Button A: X+94, Y+34
+        Button B: X+22, Y+67
+        Prize: X=9223372036854775000, Y=5400";
+        assert_eq!(
+            solve::<Day13>(s, 2),
+            Err("arithmetic overflow in prize offset".to_string())
+        );
+        assert_eq!(
+            solve::<Day13>(s, 1),
+            Err("arithmetic overflow in cross product".to_string())
+        );
+    }
+
+    #[test]
+    fn pattern_mismatch() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                     Button A: X+26, Y+66\nButton C: X+67, Y+21\nPrize: X=12748, Y=12176";
+        let error = parse_string(input).unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (13, 6, 8));
+        assert_eq!(error.text, "C: X+67, Y+21");
+        assert_eq!(error.message, "expected \"B:\"");
+        let error = ClawMachine::from("Button A: X+94, Y+34\nButton B: X+22, Y+6x").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 18, "+6x")
+        );
+        let error = ClawMachine::from("Button A: X+94, Y+34").unwrap_err();
+        assert_eq!(error.message, "missing line");
+    }
+
+    #[test]
+    fn line_endings() {
+        let lf = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                  Button A: X+26, Y+66\nButton B: X+67, Y+21\nPrize: X=12748, Y=12176\n";
+        let crlf = lf.replace('\n', "\r\n");
+        for input in [lf, lf.trim_end(), &crlf] {
+            assert_eq!(solve::<Day13>(input, 1), Ok("280".to_string()));
+        }
     }
 }
@@ -1,37 +1,58 @@
-use std::fs;
+use crate::answer::{Answer, Unsolvable};
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-const FILE_NAME: &'static str = "input_day2.txt";
+const DAY: u8 = 2;
 const MAX_STEP: i32 = 3;
 const MIN_STEP: i32 = 1;
 
-pub fn main() {
-    let file_path = format!("artifacts/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let parsed = parse_string(&input);
-    println!("{:?}", &count_safe_lists(&parsed));
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(reports: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(count_safe_lists(reports, is_safe_part1).into())
+    }
+    fn part2(reports: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(count_safe_lists(reports, is_safe_part2).into())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
+    let parsed = parse_string(input)?;
+    println!("{:?}", &count_safe_lists(&parsed, is_safe_part1));
+    println!("{:?}", &count_safe_lists(&parsed, is_safe_part2));
+    Ok(())
 }
 
-fn parse_string(input: &str) -> Vec<Vec<i32>> {
+pub fn parse_string(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
     let mut result: Vec<Vec<i32>> = Vec::new();
     for line in input.lines() {
-        let new_list: Vec<i32> = parse_line(line);
+        let new_list: Vec<i32> = parse_line(line).map_err(|error| error.within(input, line))?;
         result.push(new_list)
     }
-    result
+    Ok(result)
 }
 
-fn parse_line(line: &str) -> Vec<i32> {
+fn parse_line(line: &str) -> Result<Vec<i32>, ParseError> {
     line.split_whitespace()
-        .map(|num| num.parse::<i32>().expect("Invalid number"))
+        .map(|num| {
+            num.parse::<i32>()
+                .map_err(|_| ParseError::new(DAY, line, num, "invalid number"))
+        })
         .collect()
 }
-fn is_safe_part1(list: &Vec<i32>) -> bool {
-    let mut reversed_list = list.clone();
+pub fn is_safe_part1(list: &[i32]) -> bool {
+    let mut reversed_list = list.to_vec();
     reversed_list.reverse();
-    check_safety_one_direction_part1(&list) || check_safety_one_direction_part1(&reversed_list)
+    check_safety_one_direction_part1(list) || check_safety_one_direction_part1(&reversed_list)
 }
 
-fn check_safety_one_direction_part1(list: &Vec<i32>) -> bool {
+fn check_safety_one_direction_part1(list: &[i32]) -> bool {
     use std::ops::Range;
 
     for i in (Range {
@@ -49,29 +70,27 @@ fn check_safety_one_direction_part1(list: &Vec<i32>) -> bool {
     true
 }
 
-fn is_safe_part2(list: &Vec<i32>) -> bool {
-    let mut reversed_list = list.clone();
+pub fn is_safe_part2(list: &[i32]) -> bool {
+    let mut reversed_list = list.to_vec();
     reversed_list.reverse();
-    check_safety_one_direction_part2(&list) || check_safety_one_direction_part2(&reversed_list)
+    check_safety_one_direction_part2(list) || check_safety_one_direction_part2(&reversed_list)
 }
 
-fn check_safety_one_direction_part2(list: &Vec<i32>) -> bool {
-    if let Some(index) =  find_unsafe_index(list){
+fn check_safety_one_direction_part2(list: &[i32]) -> bool {
+    if let Some(index) = find_unsafe_index(list) {
         tolerate_a_single_bad_level(list, index)
+    } else {
+        true
     }
-    else { true }
 }
 
-fn tolerate_a_single_bad_level(list: &Vec<i32>, bad_level: usize) -> bool {
-    tolerate_ith_level(list, bad_level) || tolerate_ith_level(list, bad_level+1)
+fn tolerate_a_single_bad_level(list: &[i32], bad_level: usize) -> bool {
+    tolerate_ith_level(list, bad_level) || tolerate_ith_level(list, bad_level + 1)
 }
 
-fn find_unsafe_index(list: &Vec<i32>) -> Option<usize> {
-
-
-    for i in 0..list.len() - 1{
+fn find_unsafe_index(list: &[i32]) -> Option<usize> {
+    for i in 0..list.len() - 1 {
         if list[i + 1] - list[i] > MAX_STEP {
-
             return Some(i);
         }
         if list[i + 1] - list[i] < MIN_STEP {
@@ -82,12 +101,12 @@ fn find_unsafe_index(list: &Vec<i32>) -> Option<usize> {
     None
 }
 
-fn tolerate_ith_level(list: &Vec<i32>, i: usize) -> bool{
+fn tolerate_ith_level(list: &[i32], i: usize) -> bool {
     let new_list = clone_vec_without_ith_item(list, i);
     check_safety_one_direction_part1(&new_list)
 }
 
-fn clone_vec_without_ith_item<T: Clone>(list: &Vec<T>, i: usize) -> Vec<T> {
+fn clone_vec_without_ith_item<T: Clone>(list: &[T], i: usize) -> Vec<T> {
     list.iter()
         .enumerate()
         .filter(|&(index, _)| index != i) // Exclude item at index `i`
@@ -95,39 +114,42 @@ fn clone_vec_without_ith_item<T: Clone>(list: &Vec<T>, i: usize) -> Vec<T> {
         .collect()
 }
 
-fn count_safe_lists(input: &Vec<Vec<i32>>) -> usize {
-    input
-        .iter()
-        .filter(|list| is_safe_part2(list))
-        .count()
+pub fn count_safe_lists(input: &[Vec<i32>], is_safe: fn(&[i32]) -> bool) -> usize {
+    input.iter().filter(|list| is_safe(list)).count()
 }
 
-
 #[cfg(test)]
 pub mod tests {
     use super::*;
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_string("7 6 4 2 1\n1 2 7 8 9\n9 7 six 2 1").unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (2, 3, 5));
+        assert_eq!(error.text, "six");
+    }
     #[test]
     fn test_safe_part1() {
-        assert_eq!(is_safe_part1(&parse_line("7 6 4 2 1")), true);
-        assert_eq!(is_safe_part1(&parse_line("1 3 6 7 9")), true);
+        assert!(is_safe_part1(&parse_line("7 6 4 2 1").unwrap()));
+        assert!(is_safe_part1(&parse_line("1 3 6 7 9").unwrap()));
     }
     #[test]
     fn test_unsafe_part1() {
-        assert_eq!(is_safe_part1(&parse_line("1 2 7 8 9")), false);
-        assert_eq!(is_safe_part1(&parse_line("9 7 6 2 1")), false);
-        assert_eq!(is_safe_part1(&parse_line("1 3 2 4 5")), false);
-        assert_eq!(is_safe_part1(&parse_line("8 6 4 4 1")), false);
+        assert!(!is_safe_part1(&parse_line("1 2 7 8 9").unwrap()));
+        assert!(!is_safe_part1(&parse_line("9 7 6 2 1").unwrap()));
+        assert!(!is_safe_part1(&parse_line("1 3 2 4 5").unwrap()));
+        assert!(!is_safe_part1(&parse_line("8 6 4 4 1").unwrap()));
     }
     #[test]
     fn test_safe_part2() {
-        assert_eq!(is_safe_part2(&parse_line("7 6 4 2 1")), true);
-        assert_eq!(is_safe_part2(&parse_line("1 3 6 7 9")), true);
-        assert_eq!(is_safe_part2(&parse_line("1 3 2 4 5")), true);
-        assert_eq!(is_safe_part2(&parse_line("8 6 4 4 1")), true);
+        assert!(is_safe_part2(&parse_line("7 6 4 2 1").unwrap()));
+        assert!(is_safe_part2(&parse_line("1 3 6 7 9").unwrap()));
+        assert!(is_safe_part2(&parse_line("1 3 2 4 5").unwrap()));
+        assert!(is_safe_part2(&parse_line("8 6 4 4 1").unwrap()));
     }
     #[test]
     fn test_unsafe_part2() {
-        assert_eq!(is_safe_part2(&parse_line("1 2 7 8 9")), false);
-        assert_eq!(is_safe_part2(&parse_line("9 7 6 2 1")), false);
+        assert!(!is_safe_part2(&parse_line("1 2 7 8 9").unwrap()));
+        assert!(!is_safe_part2(&parse_line("9 7 6 2 1").unwrap()));
     }
 }
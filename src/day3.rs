@@ -1,26 +1,43 @@
+use crate::answer::{self, Answer, Overflow, Unsolvable};
+use crate::error::ParseError;
+use crate::solution::Solution;
 use regex::Regex;
-use std::fs;
 
-const FILE_NAME: &'static str = "input_day3.txt";
 const REGEX_PART_1: &str = r"mul\((\d+),(\d+)\)";
 const REGEX_PART_2: &str = r"mul\((\d+),(\d+)\)|don't|do";
 
-pub fn main() {
-    println!("{}", "this is main");
-    let file_path = format!("artifacts/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    main_part2(&input);
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(input.to_owned())
+    }
+    fn part1(memory: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(sum_multiplications(memory)?.into())
+    }
+    fn part2(memory: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(sum_enabled_multiplications(memory)?.into())
+    }
 }
 
-fn main_part1(input: &str) {
-    let result: i32 = find_regex_in_str(REGEX_PART_1, input)
+pub fn main(input: &str) -> Result<(), ParseError> {
+    println!("this is main");
+    println!("{}", answer::show(sum_multiplications(input)));
+    println!("{}", answer::show(sum_enabled_multiplications(input)));
+    Ok(())
+}
+
+pub fn sum_multiplications(input: &str) -> Result<i32, Overflow> {
+    find_regex_in_str(REGEX_PART_1, input)
         .iter()
-        .map(|expression| parse_mul_arg(expression))
-        .sum();
-    println!("{}", result);
+        .try_fold(0, |total, expression| {
+            answer::add(total, parse_mul_arg(expression)?, "sum of multiplications")
+        })
 }
 
-fn main_part2(input: &str) {
+pub fn sum_enabled_multiplications(input: &str) -> Result<i32, Overflow> {
     let mut active = true;
 
     let matches = find_regex_in_str(REGEX_PART_2, input);
@@ -31,15 +48,15 @@ fn main_part2(input: &str) {
             "don't" => active = false,
             _ => {
                 if active {
-                    result += parse_mul_arg(item)
+                    result = answer::add(result, parse_mul_arg(item)?, "sum of multiplications")?
                 }
             }
         }
     }
-    println!("{}", result)
+    Ok(result)
 }
 
-fn parse_mul_arg(input: &str) -> i32 {
+fn parse_mul_arg(input: &str) -> Result<i32, Overflow> {
     let parts: Vec<_> = input
         .strip_prefix("mul(")
         .unwrap()
@@ -47,13 +64,14 @@ fn parse_mul_arg(input: &str) -> i32 {
         .unwrap()
         .split(",")
         .collect();
-    let a: i32 = convert_str_to_i32(parts[0]);
-    let b: i32 = convert_str_to_i32(parts[1]);
-    a * b
+    let a: i32 = convert_str_to_i32(parts[0])?;
+    let b: i32 = convert_str_to_i32(parts[1])?;
+    answer::mul(a, b, "mul")
 }
 
-fn convert_str_to_i32(s: &str) -> i32 {
-    s.parse().expect(&format!("{}{}", "can't parse", s))
+// the regex only lets digits through, so an operand can only fail by not fitting
+fn convert_str_to_i32(s: &str) -> Result<i32, Overflow> {
+    s.parse().map_err(|_| Overflow { operation: "mul" })
 }
 fn find_regex_in_str<'a>(pattern: &'a str, text: &'a str) -> Vec<&'a str> {
     let re = Regex::new(pattern).expect("Invalid regex pattern");
@@ -65,7 +83,16 @@ pub mod tests {
     use super::*;
     #[test]
     fn test_parse_mul_arg() {
-        assert_eq!(parse_mul_arg("mul(2,4)"), 8);
+        assert_eq!(parse_mul_arg("mul(2,4)"), Ok(8));
+        assert_eq!(
+            parse_mul_arg("mul(65536,32768)"),
+            Err(Overflow { operation: "mul" })
+        );
+        assert_eq!(
+            parse_mul_arg("mul(99999999999,1)"),
+            Err(Overflow { operation: "mul" })
+        );
+        assert!(sum_multiplications("xmul(2,4)mul(99999999999,1)").is_err());
     }
     #[test]
     fn test_find_regex_rand() {
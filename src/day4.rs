@@ -1,177 +1,153 @@
-use std::fs;
+use crate::answer::{Answer, Unsolvable};
+use crate::day4_part2;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::solution::Solution;
 
-pub fn main() {
-    println!("this is main");
-    let file_path = "artifacts/input_files/input_day4.txt";
-    let raw_input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let result = count_all(&raw_input);
-    println!("{result}")
-}
+const DAY: u8 = 4;
+const WORD: &str = "XMAS";
 
-fn count_all(raw_input: &str) -> usize {
-    let forward_input = parse_string(raw_input);
-    let reverse_input = reverse_vec(&forward_input);
-    let line_size = count_columns(raw_input);
-    let result: usize = [forward_input, reverse_input]
-        .iter()
-        .map(|input| {
-            count_xmas(input, 1, line_size)
-                + count_xmas(input, line_size, line_size)
-                + count_xmas(input, line_size + 1, line_size)
-                + count_xmas(input, line_size - 1, line_size)
-        })
-        .sum();
-    result
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(grid: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(count_all_letters(grid).into())
+    }
+    fn part2(grid: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(day4_part2::count_xmas(grid).into())
+    }
 }
 
-fn count_columns(input: &str) -> usize {
-    input.split_once('\r').unwrap().0.len()
+pub fn main(raw_input: &str) -> Result<(), ParseError> {
+    println!("this is main");
+    let result = count_all_letters(&parse_string(raw_input)?);
+    println!("{result}");
+    Ok(())
 }
 
-fn parse_string(input: &str) -> Vec<char> {
-    input.chars().filter(|&c| c != '\r').collect()
+// XMAS can be written forwards or backwards along rows, columns and both diagonals
+pub fn count_all_letters(grid: &Grid<char>) -> usize {
+    count_lines(grid.rows().map(|row| row.iter()))
+        + count_lines(grid.columns())
+        + count_lines(grid.diagonals())
+        + count_lines(grid.anti_diagonals())
 }
-fn count_xmas(input: &[char], jump: usize, line_size: usize) -> usize {
-    let letter_count = 1 + jump * 3;
-    let new_line = if letter_count >= line_size { 1 } else { 0 };
-    input
-        .windows(letter_count + new_line * 3)
-        .filter(|&window| {
-            window[0] == 'X'
-                && window[jump + new_line] == 'M'
-                && window[(jump + new_line) * 2] == 'A'
-                && window[(jump + new_line) * 3] == 'S'
-        })
-        .count()
+
+pub fn parse_string(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::chars(DAY, input)
 }
 
-fn reverse_vec<T: Clone>(vec: &[T]) -> Vec<T> {
-    vec.iter().cloned().rev().collect()
+fn count_lines<'a, L>(lines: impl Iterator<Item = L>) -> usize
+where
+    L: Iterator<Item = &'a char>,
+{
+    let backwards: String = WORD.chars().rev().collect();
+    lines
+        .map(|line| {
+            let line: String = line.collect();
+            line.matches(WORD).count() + line.matches(&backwards).count()
+        })
+        .sum()
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::solution::solve;
+    use std::fs;
+
+    fn load(name: &str) -> Grid<char> {
+        let file_path = format!("artifacts/test_files/day4/{name}.txt");
+        parse_string(&fs::read_to_string(file_path).unwrap()).unwrap()
+    }
 
     #[test]
-    fn test_count_columns() {
-        let file_path = "artifacts/test_files/day4/day4-one-vertical.txt";
-        let input = fs::read_to_string(file_path).unwrap();
-        let result = count_columns(&input);
-        assert_eq!(result, 5);
+    fn test_grid_size() {
+        let grid = load("day4-one-vertical");
+        assert_eq!((grid.width(), grid.height()), (5, 5));
     }
 
     #[test]
     fn test_parse_string() {
-        let file_path = "artifacts/test_files/day4/day4-one-vertical.txt";
-        let input = fs::read_to_string(file_path).unwrap();
-        let result = parse_string(&input);
-        println!("{:?}", result);
-        assert_eq!(result.len(), 29);
-        assert_eq!(result[0], 'O');
-        assert_eq!(result[8], 'X');
-        assert_eq!(result[14], 'M');
-        assert_eq!(result[20], 'A');
-        assert_eq!(result[26], 'S');
+        let grid = load("day4-one-vertical");
+        assert_eq!(grid[(0, 0)], 'O');
+        assert_eq!(grid[(2, 1)], 'X');
+        assert_eq!(grid[(2, 2)], 'M');
+        assert_eq!(grid[(2, 3)], 'A');
+        assert_eq!(grid[(2, 4)], 'S');
     }
 
     #[test]
     fn test_find_horizontal_backwards() {
-        let file_path = "artifacts/test_files/day4/day4-one-horizontal-backwards.txt";
-        let raw_input = &fs::read_to_string(file_path).unwrap();
-        let input = reverse_vec(&parse_string(&raw_input));
-        assert_eq!(count_xmas(&input, 1, count_columns(raw_input)), 1);
+        let grid = load("day4-one-horizontal-backwards");
+        assert_eq!(count_lines(grid.rows().map(|row| row.iter())), 1);
     }
 
     #[test]
     fn test_find_horizontal() {
-        let file_path = "artifacts/test_files/day4/day4-one-horizontal.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-        let input = parse_string(&raw_input);
-        assert_eq!(count_xmas(&input, 1, count_columns(&raw_input)), 1);
+        let grid = load("day4-one-horizontal");
+        assert_eq!(count_lines(grid.rows().map(|row| row.iter())), 1);
     }
     #[test]
     fn test_find_something() {
-        let file_path = "artifacts/test_files/day4/day4-one-horizontal.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-        let input = parse_string(&raw_input);
-        assert!(count_xmas(&input, 1, count_columns(&raw_input)) >= 1);
+        let grid = load("day4-one-horizontal");
+        assert!(count_all_letters(&grid) >= 1);
     }
     #[test]
     fn test_dont_find_wraps() {
-        let file_path = "artifacts/test_files/day4/day4-one-horizontal-wrap.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-
-        let input = parse_string(&raw_input);
-        assert_eq!(count_xmas(&input, 1, count_columns(&raw_input)), 1);
+        let grid = load("day4-one-horizontal-wrap");
+        assert_eq!(count_lines(grid.rows().map(|row| row.iter())), 1);
     }
     #[test]
     fn test_dont_find_wraps_backwards() {
-        let file_path = "artifacts/test_files/day4/day4-one-horizontal-backwards-wrap.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-
-        let input = reverse_vec(&parse_string(&raw_input));
-        assert_eq!(count_xmas(&input, 1, count_columns(&raw_input)), 1);
+        let grid = load("day4-one-horizontal-backwards-wrap");
+        assert_eq!(count_lines(grid.rows().map(|row| row.iter())), 1);
     }
 
     #[test]
     fn test_find_vertical() {
-        let file_path = "artifacts/test_files/day4/day4-one-vertical.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-        let input = parse_string(&raw_input);
-        assert_eq!(
-            count_xmas(&input, count_columns(&raw_input), count_columns(&raw_input)),
-            1
-        );
+        let grid = load("day4-one-vertical");
+        assert_eq!(count_lines(grid.columns()), 1);
+        assert_eq!(count_all_letters(&grid), 1);
     }
     #[test]
     fn test_find_vertical_backwards() {
-        let file_path = "artifacts/test_files/day4/day4-one-verticalc-backwards.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-
-        let input = reverse_vec(&parse_string(&raw_input));
-        assert_eq!(
-            count_xmas(&input, count_columns(&raw_input), count_columns(&raw_input)),
-            1
-        );
+        let grid = load("day4-one-verticalc-backwards");
+        assert_eq!(count_lines(grid.columns()), 1);
+        assert_eq!(count_all_letters(&grid), 1);
     }
 
     #[test]
     fn test_find_diagonal() {
-        let file_path = "artifacts/test_files/day4/day4-one-diagonal.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-
-        let input = parse_string(&raw_input);
-        assert_eq!(
-            count_xmas(
-                &input,
-                count_columns(&raw_input) + 1,
-                count_columns(&raw_input)
-            ),
-            1
-        );
+        let grid = load("day4-one-diagonal");
+        assert_eq!(count_lines(grid.diagonals()), 1);
+        assert_eq!(count_all_letters(&grid), 1);
     }
     #[test]
     fn test_find_diagonal_backwards() {
-        let file_path = "artifacts/test_files/day4/day4-one-diagonal-backwards.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
-
-        let input = reverse_vec(&parse_string(&raw_input));
-        assert_eq!(
-            count_xmas(
-                &input,
-                count_columns(&raw_input) + 1,
-                count_columns(&raw_input)
-            ),
-            1
-        );
+        let grid = load("day4-one-diagonal-backwards");
+        assert_eq!(count_lines(grid.diagonals()), 1);
+        assert_eq!(count_all_letters(&grid), 1);
     }
     #[test]
     fn test_count_all() {
-        let file_path = "artifacts/test_files/day4/full_example.txt";
-        let raw_input = fs::read_to_string(file_path).unwrap();
+        let grid = load("full_example");
+        assert_eq!(count_all_letters(&grid), 18);
+    }
 
-        let result = count_all(&raw_input);
-        assert_eq!(result, 18);
+    #[test]
+    fn line_endings() {
+        let file_path = "artifacts/test_files/day4/full_example.txt";
+        let lf = fs::read_to_string(file_path).unwrap();
+        let crlf = lf.replace('\n', "\r\n") + "\r\n";
+        for input in [&lf, &crlf, &(lf.clone() + "\n")] {
+            assert_eq!(solve::<Day4>(input, 1), Ok("18".to_string()));
+            assert_eq!(solve::<Day4>(input, 2), Ok("9".to_string()));
+        }
     }
 }
@@ -1,71 +1,76 @@
-use std::fs;
+use crate::error::ParseError;
+use crate::grid::{Grid, Point};
+use crate::position::Direction::{self, DownLeft, DownRight, UpLeft, UpRight};
 
-pub fn main() {
+const DAY: u8 = 4;
+
+pub fn main(raw_input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = "artifacts/input_files/input_day4.txt";
-    let raw_input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let result = count_xmas(parse_string(&raw_input), count_columns(&raw_input));
-    println!("{result}")
+    let result = count_xmas(&Grid::chars(DAY, raw_input)?);
+    println!("{result}");
+    Ok(())
 }
 
-fn count_columns(input: &str) -> usize {
-    input.split_once('\r').unwrap().0.len()
+pub fn count_xmas(grid: &Grid<char>) -> usize {
+    grid.iter()
+        .filter(|&(point, &letter)| letter == 'A' && is_crossed(grid, point))
+        .count()
 }
 
-fn parse_string(input: &str) -> Vec<char> {
-    input.chars().filter(|&c| c != '\r').collect()
-}
-fn count_xmas(input: Vec<char>, line_size: usize) -> usize {
-    input
-        .windows(line_size * 2 + 5)
-        .filter(|&window| {
-            [window[0], window[line_size * 2 + 4]].contains(&'M')
-                && [window[0], window[line_size * 2 + 4]].contains(&'S')
-                && [window[2], window[line_size * 2 + 2]].contains(&'M')
-                && [window[2], window[line_size * 2 + 2]].contains(&'S')
-                && window[line_size + 2] == 'A'
-        })
-        .count()
+// both diagonals through the A read MAS, in either direction
+fn is_crossed(grid: &Grid<char>, point: Point) -> bool {
+    let corner = |direction| grid.step(point, direction).map(|corner| grid[corner]);
+    let is_mas = |from: Direction, to: Direction| {
+        matches!(
+            (corner(from), corner(to)),
+            (Some('M'), Some('S')) | (Some('S'), Some('M'))
+        )
+    };
+    is_mas(UpLeft, DownRight) && is_mas(UpRight, DownLeft)
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::input::normalize;
+    use std::fs;
 
-    #[test]
-    fn test_count_columns() {
-        let file_path = "artifacts/test_files/day4/day4-one-vertical.txt";
-        let input = fs::read_to_string(file_path).unwrap();
-        let result = count_columns(&input);
-        assert_eq!(result, 5);
+    fn load(name: &str) -> Grid<char> {
+        let file_path = format!("artifacts/test_files/day4/{name}.txt");
+        Grid::chars(DAY, &fs::read_to_string(file_path).unwrap()).unwrap()
     }
 
     #[test]
     fn test_parse_string() {
-        let file_path = "artifacts/test_files/day4/day4-one-vertical.txt";
-        let input = fs::read_to_string(file_path).unwrap();
-        let result = parse_string(&input);
-        println!("{:?}", result);
-        assert_eq!(result.len(), 29);
-        assert_eq!(result[0], 'O');
-        assert_eq!(result[8], 'X');
-        assert_eq!(result[14], 'M');
-        assert_eq!(result[20], 'A');
-        assert_eq!(result[26], 'S');
+        let grid = load("day4-one-vertical");
+        assert_eq!((grid.width(), grid.height()), (5, 5));
+        assert_eq!(grid[(0, 0)], 'O');
+        assert_eq!(grid[(2, 1)], 'X');
+        assert_eq!(grid[(2, 4)], 'S');
     }
 
     #[test]
     fn test_trivial() {
-        let file_path = "artifacts/test_files/day4/part2-3x3.txt";
-        let raw_input = &fs::read_to_string(file_path).unwrap();
-        let input = parse_string(&raw_input);
-        assert_eq!(count_xmas(input, count_columns(raw_input)), 1);
+        assert_eq!(count_xmas(&load("part2-3x3")), 1);
     }
     #[test]
     fn test_10x10() {
+        assert_eq!(count_xmas(&load("part2-10x10")), 9);
+    }
+
+    #[test]
+    fn test_edges() {
+        assert!(!is_crossed(&load("part2-3x3"), (0, 1)));
+        assert!(is_crossed(&load("part2-3x3"), (1, 1)));
+    }
+
+    #[test]
+    fn line_endings() {
         let file_path = "artifacts/test_files/day4/part2-10x10.txt";
-        let raw_input = &fs::read_to_string(file_path).unwrap();
-        let input = parse_string(&raw_input);
-        assert_eq!(count_xmas(input, count_columns(raw_input)), 9);
+        let lf = fs::read_to_string(file_path).unwrap();
+        let crlf = normalize(&lf.replace('\n', "\r\n")).into_owned();
+        let grid = Grid::chars(DAY, &crlf).unwrap();
+        assert_eq!(grid, Grid::chars(DAY, &lf).unwrap());
+        assert_eq!(count_xmas(&grid), 9);
     }
 }
@@ -1,40 +1,78 @@
-use itertools::{Itertools, Update};
-use std::fs;
+use crate::answer::{self, Answer, CheckedIterator, Overflow, Unsolvable};
+use crate::day5_part2;
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-const FILE_NAME: &'static str = "input_day5.txt";
+const DAY: u8 = 5;
 
-pub fn main() {
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (Vec<Vec<i32>>, Vec<(i32, i32)>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1((updates, rules): &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(sum_correct_middles(updates, rules)?.into())
+    }
+    fn part2((updates, rules): &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(day5_part2::sum_corrected_middles(updates, rules)?.into())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let (updates, rules) = parse_string(&input);
+    let (updates, rules) = parse_string(input)?;
+    let result = sum_correct_middles(&updates, &rules);
+    println!("{}", answer::show(result));
+    Ok(())
+}
+
+pub fn sum_correct_middles(updates: &[Vec<i32>], rules: &[(i32, i32)]) -> Result<i32, Overflow> {
     let correct_updates: Vec<_> = updates
         .iter()
-        .filter(|&update| is_update_correct(&update, &rules))
+        .filter(|&update| is_update_correct(update, rules))
         .collect();
-    let result = sum_middles(&correct_updates);
-    println!("{}", result);
+    sum_middles(&correct_updates)
 }
 
-fn parse_string(input: &str) -> (Vec<Vec<i32>>, Vec<(i32, i32)>) {
-    let Some((rules_raw, updates_raw)) = input.split_once("\r\n\r\n") else {
-        panic!("this shouldn't happen!")
+pub type Parsed = (Vec<Vec<i32>>, Vec<(i32, i32)>);
+
+pub fn parse_string(input: &str) -> Result<Parsed, ParseError> {
+    let Some((rules_raw, updates_raw)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            "",
+            "expected a blank line between rules and updates",
+        ));
     };
-    let updates: Vec<Vec<i32>> = updates_raw
+    let updates = updates_raw
         .lines()
         .map(|line| {
             line.split(',')
-                .map(|s| s.parse().unwrap())
-                .collect::<Vec<i32>>()
+                .map(|s| parse_number(input, s))
+                .collect::<Result<Vec<i32>, _>>()
         })
-        .collect();
-    let rules: Vec<(i32, i32)> = rules_raw
+        .collect::<Result<Vec<_>, _>>()?;
+    let rules = rules_raw
         .lines()
-        .map(|l| l.split_once('|').unwrap())
-        .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-        .collect();
+        .map(|line| {
+            let (a, b) = line
+                .split_once('|')
+                .ok_or_else(|| ParseError::new(DAY, input, line, "expected a rule like 47|53"))?;
+            Ok((parse_number(input, a)?, parse_number(input, b)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
-    (updates, rules)
+    Ok((updates, rules))
+}
+
+fn parse_number(input: &str, number: &str) -> Result<i32, ParseError> {
+    number
+        .parse()
+        .map_err(|_| ParseError::new(DAY, input, number, "invalid number"))
 }
 
 fn does_break_rule(update: &[i32], rule: &(i32, i32)) -> bool {
@@ -59,12 +97,16 @@ fn get_middle(update: &[i32]) -> i32 {
     update[update.len() / 2]
 }
 
-fn sum_middles(updates: &Vec<&Vec<i32>>) -> i32 {
-    updates.iter().map(|u| get_middle(u)).sum()
+fn sum_middles(updates: &Vec<&Vec<i32>>) -> Result<i32, Overflow> {
+    updates
+        .iter()
+        .map(|u| get_middle(u))
+        .checked_sum("sum of middles")
 }
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::solution::solve;
     #[test]
     fn rule_breaking() {
         let rule = (2, 1);
@@ -125,6 +167,44 @@ pub mod tests {
         let binding2 = vec![1, 2, 3];
         let binding3 = vec![1, 2, 3, 4, 5];
         let updates = vec![&binding1, &binding2, &binding3];
-        assert_eq!(sum_middles(&updates), 9);
+        assert_eq!(sum_middles(&updates), Ok(9));
+        let large = vec![i32::MAX];
+        assert!(sum_middles(&vec![&large, &binding2]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_string("47|53\n97-13\n\n75,47").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "97-13");
+        let error = parse_string("47|53\n\n75,4x,61").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 4, "4x")
+        );
+        assert!(parse_string("47|53\n75,47").is_err());
+    }
+
+    const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n\
+                           61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\
+                           \n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47\n";
+
+    #[test]
+    fn line_endings() {
+        let crlf = EXAMPLE.replace('\n', "\r\n");
+        for input in [EXAMPLE, EXAMPLE.trim_end(), &crlf] {
+            assert_eq!(solve::<Day5>(input, 1), Ok("143".to_string()));
+            assert_eq!(solve::<Day5>(input, 2), Ok("123".to_string()));
+        }
+    }
+
+    #[test]
+    fn cyclic_rules() {
+        let input = "1|2\n2|1\n\n1,2";
+        assert_eq!(solve::<Day5>(input, 1), Ok("0".to_string()));
+        assert_eq!(
+            solve::<Day5>(input, 2),
+            Err("no valid order, cycle 1 -> 2 -> 1".to_string())
+        );
     }
 }
@@ -1,26 +1,31 @@
-use std::collections::{HashMap, HashSet};
-use std::fs;
+use crate::answer::{self, CheckedIterator, Overflow, Unsolvable};
+use crate::error::ParseError;
+use crate::graph::{Cycle, Graph};
 
-const FILE_NAME: &str = "input_day5.txt";
+const DAY: u8 = 5;
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let (updates, rules) = parse_string(&input);
-    let incorrect_updates: Vec<_> = updates
+    let (updates, rules) = parse_string(input)?;
+    let result = sum_corrected_middles(&updates, &rules);
+    println!("{}", answer::show(result));
+    Ok(())
+}
+
+// rules that form a cycle among the pages of an update leave it without a valid order
+pub fn sum_corrected_middles(
+    updates: &[Vec<i32>],
+    rules: &[(i32, i32)],
+) -> Result<i32, Unsolvable> {
+    let incorrect_updates = updates
         .iter()
-        .filter(|update| !is_update_correct(update, &rules))
-        .map(|update| correct_update(update, &rules))
-        .collect();
-    let result = sum_middles(&incorrect_updates);
-    println!("{}", result);
+        .filter(|update| !is_update_correct(update, rules))
+        .map(|update| correct_update(update, rules))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(sum_middles(&incorrect_updates)?)
 }
 
-fn filter_irrelevant_rules(
-    rules_unfiltered: &[(i32, i32)],
-    update: &[i32],
-) -> Vec<(i32, i32)> {
+fn filter_irrelevant_rules(rules_unfiltered: &[(i32, i32)], update: &[i32]) -> Vec<(i32, i32)> {
     rules_unfiltered
         .iter()
         .filter(|(first, later)| update.contains(first) && update.contains(later))
@@ -28,25 +33,42 @@ fn filter_irrelevant_rules(
         .collect()
 }
 
-fn parse_string(input: &str) -> (Vec<Vec<i32>>, Vec<(i32, i32)>) {
-    let Some((rules_raw, updates_raw)) = input.split_once("\r\n\r\n") else {
-        panic!("this shouldn't happen!")
+type Parsed = (Vec<Vec<i32>>, Vec<(i32, i32)>);
+
+fn parse_string(input: &str) -> Result<Parsed, ParseError> {
+    let Some((rules_raw, updates_raw)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(
+            DAY,
+            input,
+            "",
+            "expected a blank line between rules and updates",
+        ));
     };
-    let updates: Vec<Vec<i32>> = updates_raw
+    let updates = updates_raw
         .lines()
         .map(|line| {
             line.split(',')
-                .map(|s| s.parse().unwrap())
-                .collect::<Vec<i32>>()
+                .map(|s| parse_number(input, s))
+                .collect::<Result<Vec<i32>, _>>()
         })
-        .collect();
-    let rules: Vec<(i32, i32)> = rules_raw
+        .collect::<Result<Vec<_>, _>>()?;
+    let rules = rules_raw
         .lines()
-        .map(|l| l.split_once('|').unwrap())
-        .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-        .collect();
+        .map(|line| {
+            let (a, b) = line
+                .split_once('|')
+                .ok_or_else(|| ParseError::new(DAY, input, line, "expected a rule like 47|53"))?;
+            Ok((parse_number(input, a)?, parse_number(input, b)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((updates, rules))
+}
 
-    (updates, rules)
+fn parse_number(input: &str, number: &str) -> Result<i32, ParseError> {
+    number
+        .parse()
+        .map_err(|_| ParseError::new(DAY, input, number, "invalid number"))
 }
 
 fn does_break_rule(update: &[i32], rule: &(i32, i32)) -> bool {
@@ -71,83 +93,30 @@ fn get_middle(update: &[i32]) -> i32 {
     update[update.len() / 2]
 }
 
-fn sum_middles(updates: &[Vec<i32>]) -> i32 {
-    updates.iter().map(|u| get_middle(u)).sum()
+fn sum_middles(updates: &[Vec<i32>]) -> Result<i32, Overflow> {
+    updates
+        .iter()
+        .map(|u| get_middle(u))
+        .checked_sum("sum of middles")
 }
 
-fn create_constraint_graph(rules: &[(i32, i32)]) -> HashMap<&i32, Vec<&i32>> {
-    let mut result = HashMap::new();
-    for (first, later) in rules {
-        result.entry(first).or_insert(vec![]).push(later);
-    }
-    result
+fn create_constraint_graph(rules: &[(i32, i32)]) -> Graph<i32> {
+    rules.iter().copied().collect()
 }
 
-fn visit<'a>(
-    node: &'a i32,
-    call_stack: &mut HashSet<&'a i32>,
-    visited: &mut HashSet<&'a i32>,
-    result: &mut Vec<&'a i32>,
-    constraint_graph: &&HashMap<&i32, Vec<&'a i32>>,
-) {
-    let Some(neighbors) = constraint_graph.get(node) else {
-        visited.insert(node);
-        call_stack.remove(node);
-        result.push(node);
-        return;
-    };
-
-    for neighbor in neighbors {
-        if call_stack.contains(neighbor) {
-            panic!(
-                "cyclic graph found! aborting.\nDebug data: node: {:?}\nneighbor: {:?}, call_stack: {:?}",
-                node, neighbor, call_stack
-            )
-        }
-        if !visited.contains(neighbor) {
-            call_stack.insert(neighbor);
-            visit(neighbor, call_stack, visited, result, constraint_graph)
-        }
-    }
-    visited.insert(node);
-    call_stack.remove(node);
-    result.push(node);
-}
-fn topological_sort<'a>(constraint_graph: &HashMap<&'a i32, Vec<&'a i32>>) -> Vec<&'a i32> {
-    let mut call_stack = HashSet::new();
-    let mut visited = HashSet::new();
-    let mut result = Vec::new();
-
-    for node in constraint_graph.keys() {
-        if !visited.contains(node) {
-            visit(
-                node,
-                &mut call_stack,
-                &mut visited,
-                &mut result,
-                &constraint_graph,
-            );
-        }
-    }
-    result.reverse();
-    result
-}
-
-fn correct_update(update: &[i32], rules: &[(i32,i32)]) -> Vec<i32> {
-    let rules_filtered = filter_irrelevant_rules(rules,update);
-    let constraint_graph = create_constraint_graph(&rules_filtered);
-    let sorted_constraints = topological_sort(&constraint_graph);
-
-    sorted_constraints
-        .iter()
-        .filter(|item| update.contains(item))
-        .map(|&&i| i)
-        .collect()
+pub fn correct_update(update: &[i32], rules: &[(i32, i32)]) -> Result<Vec<i32>, Cycle<i32>> {
+    let rules_filtered = filter_irrelevant_rules(rules, update);
+    let mut constraint_graph = create_constraint_graph(&rules_filtered);
+    for &page in update {
+        constraint_graph.add_node(page);
+    }
+    constraint_graph.topological_sort()
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::input::normalize;
     #[test]
     fn rule_breaking() {
         let rule = (2, 1);
@@ -208,24 +177,24 @@ pub mod tests {
         let binding2 = vec![1, 2, 3];
         let binding3 = vec![1, 2, 3, 4, 5];
         let updates = vec![binding1, binding2, binding3];
-        assert_eq!(sum_middles(&updates), 9);
+        assert_eq!(sum_middles(&updates), Ok(9));
     }
 
     #[test]
     fn test_create_constraint_graph() {
         let rules = vec![(1, 2), (1, 3), (2, 4), (4, 5), (1, 5)];
         let graph = create_constraint_graph(&rules);
-        assert_eq!(graph.get(&1), Some(&vec![&2, &3, &5]));
-        assert_eq!(graph.get(&2), Some(&vec![&4]));
-        assert_eq!(graph.get(&4), Some(&vec![&5]));
+        assert_eq!(graph.successors(&1).collect::<Vec<_>>(), [&2, &3, &5]);
+        assert_eq!(graph.successors(&2).collect::<Vec<_>>(), [&4]);
+        assert_eq!(graph.successors(&4).collect::<Vec<_>>(), [&5]);
     }
 
     #[test]
     fn test_top_sort() {
         let rules = vec![(1, 2), (1, 3), (2, 4), (4, 5), (1, 5), (2, 3), (3, 4)];
         let graph = create_constraint_graph(&rules);
-        let sorted_graph = topological_sort(&graph);
-        assert_eq!(sorted_graph, vec![&1, &2, &3, &4, &5])
+        let sorted_graph = graph.topological_sort();
+        assert_eq!(sorted_graph, Ok(vec![1, 2, 3, 4, 5]))
     }
 
     #[test]
@@ -235,15 +204,41 @@ pub mod tests {
         let incorrect_update = vec![3, 2, 4, 1, 5];
         assert!(!is_update_correct(&incorrect_update, &rules));
 
-        let correct_update = correct_update(&incorrect_update, &rules);
+        let correct_update = correct_update(&incorrect_update, &rules).unwrap();
         assert!(is_update_correct(&correct_update, &rules));
+        assert_eq!(correct_update, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn cyclic_rules() {
+        let rules = vec![(1, 2), (2, 3), (3, 1), (4, 1)];
+        let cycle = correct_update(&[4, 3, 2, 1], &rules).unwrap_err();
+        assert_eq!(cycle.to_string(), "cycle 1 -> 2 -> 3 -> 1");
+        assert_eq!(correct_update(&[3, 4, 1], &rules), Ok(vec![4, 3, 1]));
+        let updates = vec![vec![3, 4, 1], vec![4, 3, 2, 1]];
+        assert_eq!(
+            sum_corrected_middles(&updates, &rules),
+            Err(Unsolvable::Cycle(Cycle(vec![1, 2, 3])))
+        );
     }
 
     #[test]
-    fn test_filter_irrelevant_rules(){
+    fn test_filter_irrelevant_rules() {
         let rules_unfiltered = vec![(1, 2), (1, 3), (2, 4), (4, 5), (1, 5), (2, 3), (3, 4)];
-        let update = vec![1,2,3];
+        let update = vec![1, 2, 3];
         let rules = filter_irrelevant_rules(&rules_unfiltered, &update);
         assert_eq!(rules, vec![(1, 2), (1, 3), (2, 3)])
     }
+
+    const EXAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n\
+                           61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n\
+                           \n75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47\n";
+
+    #[test]
+    fn line_endings() {
+        let crlf = EXAMPLE.replace('\n', "\r\n");
+        let expected = parse_string(&normalize(EXAMPLE)).unwrap();
+        assert_eq!(parse_string(&normalize(&crlf)), Ok(expected.clone()));
+        assert_eq!(sum_corrected_middles(&expected.0, &expected.1), Ok(123));
+    }
 }
@@ -1,27 +1,63 @@
-use crate::day6::Direction::{Down, Left, Right, Up};
+use crate::answer::{Answer, Unsolvable};
+use crate::day6_part2;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::position::Direction::{Down, Left, Right, Up};
+use crate::position::{Direction, Position};
+use crate::ppm::{Palette, Rgb};
+use crate::render::{self, Colour, Glyph, Render};
+use crate::solution::Solution;
 use std::cmp::PartialEq;
-use std::fs;
+use std::collections::HashSet;
 
-const FILE_NAME: &str = "input_day6.txt";
+const DAY: u8 = 6;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = (World, day6_part2::World);
 
-#[derive(Debug, PartialEq)]
-struct Size {
-    rows: usize,
-    columns: usize,
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((
+            WorldBuilder::build(input)?,
+            day6_part2::WorldBuilder::build(input)?,
+        ))
+    }
+    fn part1((world, _): &Self::Input) -> Result<Answer, Unsolvable> {
+        let mut world = world.clone();
+        world.run();
+        if world.state == State::Loop {
+            return Err(Unsolvable::Endless {
+                what: "the guard's walk",
+            });
+        }
+        Ok(world.count_visited_cells().into())
+    }
+    fn part2((_, world): &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(world.find_possible_loops().into())
+    }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Position {
-    x: usize,
-    y: usize,
+impl Render for Day6 {
+    const PALETTE: Palette = Palette::DEFAULT
+        .with_empty(Rgb(245, 245, 240))
+        .with(Colour::Grey, Rgb(60, 60, 60))
+        .with(Colour::Yellow, Rgb(250, 190, 60));
+
+    fn render(
+        (world, _): &Self::Input,
+        every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        let mut world = world.clone();
+        world.run_observed(|step, world| {
+            if render::is_frame(every, step) {
+                frame(&world.canvas())
+            }
+        });
+        frame(&world.canvas());
+        Ok(())
+    }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -51,32 +87,15 @@ impl Cell {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Guard {
     position: Position,
     direction: Direction,
 }
 
 impl Guard {
-    fn next_position(&self) -> Result<Position, String> {
-        let mut next_position = self.position;
-        match self.direction {
-            Up => next_position.y += 1,
-            Right => next_position.x += 1,
-            Down => {
-                if next_position.y == 0 {
-                    return Err("Invalid position!".into());
-                }
-                next_position.y -= 1
-            }
-            Left => {
-                if next_position.x == 0 {
-                    return Err("Invalid position!".into());
-                }
-                next_position.x -= 1
-            }
-        }
-        Ok(next_position)
+    fn next_position(&self) -> Option<Position> {
+        self.position + self.direction
     }
 
     fn walk(&mut self) {
@@ -84,166 +103,155 @@ impl Guard {
     }
 
     fn rotate(&mut self) {
-        let new_direction = match self.direction {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        };
-        self.direction = new_direction;
+        self.direction = self.direction.clockwise();
     }
 }
 
-#[derive(Debug, PartialOrd, PartialEq)]
+#[derive(Debug, PartialOrd, PartialEq, Clone)]
 enum State {
     NotDone,
     Done,
+    Loop,
 }
-#[derive(Debug)]
-struct World {
-    size: Size,
-    map: Vec<Vec<Cell>>,
+#[derive(Debug, Clone)]
+pub struct World {
+    map: Grid<Cell>,
     guard: Guard,
     state: State,
+    // where the guard turned and which way it faced after, a walk that repeats one never ends
+    turns: HashSet<(Position, Direction)>,
 }
 
 impl World {
-    fn run(&mut self, timeout: usize) {
+    pub fn run(&mut self) {
+        self.run_observed(|_, _| {})
+    }
+
+    // `observe` sees the world before every frame
+    pub fn run_observed(&mut self, mut observe: impl FnMut(usize, &World)) {
         let mut frame = 0;
 
-        while self.state != State::Done && frame < timeout {
+        while !self.is_done() {
+            observe(frame, self);
             self.next_frame();
             frame += 1;
         }
     }
 
     fn visit(&mut self, position: &Position) {
-        self.map[position.y][position.x] = Cell::Visited;
+        self.map[*position] = Cell::Visited;
     }
-    fn count_visited_cells(&self) -> usize {
+    pub fn count_visited_cells(&self) -> usize {
         self.map
             .iter()
-            .map(|row| row.iter().filter(|&cell| cell.is_visited()).count())
-            .sum()
+            .filter(|(_, cell)| cell.is_visited())
+            .count()
     }
 
     fn is_done(&self) -> bool {
-        self.state == State::Done
+        self.state != State::NotDone
     }
-}
 
-impl World {
-    fn get_cell(&self, position: &Position) -> Result<Cell, String> {
-        if position.y >= self.map.len() {
-            return Err("Row index out of bounds".into());
+    // the guard on top of the visited cells, it's left out once it walked off the map
+    fn canvas(&self) -> Grid<Glyph> {
+        let mut canvas = self.map.map(|cell| match cell {
+            Cell::NotVisited => Glyph::new('.'),
+            Cell::Obstruction => Glyph::new('#').fg(Colour::Grey),
+            Cell::Visited | Cell::InitialGuardPosition(_) => Glyph::new('X').fg(Colour::Yellow),
+        });
+        if self.state != State::Done {
+            let symbol = match self.guard.direction {
+                Up => '^',
+                Right => '>',
+                Down => 'v',
+                Left => '<',
+                _ => panic!("the guard only walks orthogonally"),
+            };
+            canvas[self.guard.position] = Glyph::new(symbol).fg(Colour::Red);
         }
-        if position.x >= self.map[position.y].len() {
-            return Err("Column index out of bounds".into());
-        }
-        Ok(self.map[position.y][position.x])
+        canvas
     }
-    fn next_frame(&mut self) {
-        // println!("world: {:?}", self);
+}
 
-        if self.state == State::Done {
+impl World {
+    fn next_frame(&mut self) {
+        if self.is_done() {
             return;
         }
 
-        let Ok(next_position) = &self.guard.next_position() else {
+        let Some(next_position) = self
+            .guard
+            .position
+            .step_within(self.guard.direction, self.map.size())
+        else {
             self.state = State::Done;
             return;
         };
-        match self.get_cell(next_position) {
-            Err(_) => self.state = State::Done,
-            Ok(pos) => {
-                if pos.is_empty() {
-                    self.visit(&self.guard.next_position().unwrap());
-                    self.guard.walk()
-                } else {
-                    self.guard.rotate()
-                }
+        if self.map[next_position].is_empty() {
+            self.visit(&next_position);
+            self.guard.walk()
+        } else {
+            self.guard.rotate();
+            if !self
+                .turns
+                .insert((self.guard.position, self.guard.direction))
+            {
+                self.state = State::Loop
             }
         }
     }
 }
 
-struct WorldBuilder();
+pub struct WorldBuilder();
 impl WorldBuilder {
-    fn build(input_raw: &str) -> World {
-        let input = input_raw.trim();
-        let size = Self::get_size(input);
-        let map = Self::build_map(input);
-        let guard = Self::build_guard(&map);
-        World {
-            size,
+    pub fn build(input: &str) -> Result<World, ParseError> {
+        let map = Grid::parse(DAY, input, Self::build_cell)?;
+        let guard = Self::build_guard(&map)
+            .ok_or_else(|| ParseError::new(DAY, input, "", "missing guard"))?;
+        Ok(World {
             map,
             guard,
             state: State::NotDone,
-        }
+            turns: HashSet::new(),
+        })
     }
 
-    fn get_size(input: &str) -> Size {
-        let input_lines: Vec<&str> = input.lines().collect();
-        Size {
-            rows: input_lines.len(),
-            columns: input_lines[0].len(),
+    fn build_cell(c: char) -> Result<Cell, &'static str> {
+        match c {
+            '.' => Ok(Cell::NotVisited),
+            '^' => Ok(Cell::InitialGuardPosition(Up)),
+            '>' => Ok(Cell::InitialGuardPosition(Right)),
+            '<' => Ok(Cell::InitialGuardPosition(Left)),
+            'v' | 'V' => Ok(Cell::InitialGuardPosition(Down)),
+            '#' => Ok(Cell::Obstruction),
+            _ => Err("invalid character"),
         }
     }
-
-    fn build_line(line: &str) -> Vec<Cell> {
-        line.chars()
-            .map(|c| match c {
-                '.' => Cell::NotVisited,
-                '^' => Cell::InitialGuardPosition(Up),
-                '>' => Cell::InitialGuardPosition(Right),
-                '<' => Cell::InitialGuardPosition(Left),
-                'V' => Cell::InitialGuardPosition(Down),
-                '#' => Cell::Obstruction,
-                _ => panic!("invalid character {c}!"),
-            })
-            .collect()
-    }
-    fn build_map(input: &str) -> Vec<Vec<Cell>> {
-        input
-            .lines()
-            .rev()
-            .map( WorldBuilder::build_line)
-            .collect()
-    }
-    fn build_guard(map: &[Vec<Cell>]) -> Guard {
-        let (y, x) = map
-            .iter()
-            .enumerate()
-            .find_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .find(|(_, cell)| matches!(cell, Cell::InitialGuardPosition(_)))
-                    .map(|(x, _)| (y, x))
-            })
-            .unwrap();
-        let Cell::InitialGuardPosition(direction) = map[y][x] else {
+    fn build_guard(map: &Grid<Cell>) -> Option<Guard> {
+        let (x, y) = map.position(|cell| matches!(cell, Cell::InitialGuardPosition(_)))?;
+        let Cell::InitialGuardPosition(direction) = map[(x, y)] else {
             panic!("this shouldn't happen")
         };
 
-        Guard {
-            position: Position { x, y },
+        Some(Guard {
+            position: Position::new(x, y),
             direction,
-        }
+        })
     }
 }
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut world = WorldBuilder::build(&input);
-    world.run(10_000);
+    let mut world = WorldBuilder::build(input)?;
+    world.run();
     println!("{}", world.count_visited_cells());
+    Ok(())
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::render::draw;
     #[test]
     fn new_from_input() {
         let input = r"
@@ -251,18 +259,12 @@ pub mod tests {
 ####
 ....
 ";
-        let world = WorldBuilder::build(input.trim());
-        assert_eq!(
-            world.size,
-            Size {
-                rows: 3,
-                columns: 4
-            }
-        );
-        assert_eq!(world.guard.position, Position { x: 3, y: 2 });
+        let world = WorldBuilder::build(input.trim()).unwrap();
+        assert_eq!((world.map.width(), world.map.height()), (4, 3));
+        assert_eq!(world.guard.position, Position { x: 3, y: 0 });
         assert_eq!(world.guard.direction, Up);
-        assert!(!world.map[1][3].is_empty());
-        assert!(world.map[0][3].is_empty());
+        assert!(!world.map[(3, 1)].is_empty());
+        assert!(world.map[(3, 2)].is_empty());
     }
 
     #[test]
@@ -288,11 +290,11 @@ pub mod tests {
             direction: Up,
         };
         guard.walk();
-        assert_eq!(guard.position, Position { x: 2, y: 3 });
+        assert_eq!(guard.position, Position { x: 2, y: 1 });
         guard.rotate();
         guard.walk();
         guard.walk();
-        assert_eq!(guard.position, Position { x: 4, y: 3 });
+        assert_eq!(guard.position, Position { x: 4, y: 1 });
     }
     #[test]
     fn test_next_position() {
@@ -301,17 +303,17 @@ pub mod tests {
             direction: Up,
         };
         let next_pos = guard.next_position();
-        assert_eq!(next_pos, Ok(Position { x: 2, y: 3 }));
+        assert_eq!(next_pos, Some(Position { x: 2, y: 1 }));
         assert_eq!(guard.position, Position { x: 2, y: 2 });
         guard.rotate();
         let next_pos = guard.next_position();
-        assert_eq!(next_pos, Ok(Position { x: 3, y: 2 }));
+        assert_eq!(next_pos, Some(Position { x: 3, y: 2 }));
         assert_eq!(guard.position, Position { x: 2, y: 2 });
     }
     #[test]
     fn next_frame_walk() {
         let input = r"..>.";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert_eq!(world.guard.position, Position { x: 2, y: 0 });
 
         world.next_frame();
@@ -321,7 +323,7 @@ pub mod tests {
     #[test]
     fn next_frame_rotate() {
         let input = r"..>#";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert_eq!(world.guard.position, Position { x: 2, y: 0 });
         assert_eq!(world.guard.direction, Right);
 
@@ -334,7 +336,7 @@ pub mod tests {
     #[test]
     fn test_done() {
         let input = r"...>";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert!(!world.is_done());
         world.next_frame();
         assert!(world.is_done());
@@ -343,8 +345,8 @@ pub mod tests {
     #[test]
     fn run_simulation() {
         let input = r">...";
-        let mut world = WorldBuilder::build(input);
-        world.run(5);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::Done);
         assert_eq!(world.guard.position, Position { x: 3, y: 0 });
         assert_eq!(world.count_visited_cells(), 4);
@@ -352,8 +354,8 @@ pub mod tests {
     #[test]
     fn run_simulation2() {
         let input = r">.#.";
-        let mut world = WorldBuilder::build(input);
-        world.run(5);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::Done);
         assert_eq!(world.guard.position, Position { x: 1, y: 0 });
         assert_eq!(world.count_visited_cells(), 2);
@@ -364,15 +366,15 @@ pub mod tests {
 >.#.
 ....
 .#..";
-        let mut world = WorldBuilder::build(input);
-        world.run(10);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::Done);
         assert_eq!(world.guard.position, Position { x: 0, y: 1 });
         assert_eq!(world.count_visited_cells(), 4);
     }
 
     #[test]
-    fn official_simulation(){
+    fn official_simulation() {
         let input = r"
 ....#.....
 .........#
@@ -384,8 +386,54 @@ pub mod tests {
 ........#.
 #.........
 ......#...";
-        let mut world = WorldBuilder::build(input);
-        world.run(1000);
-        assert_eq!(world.count_visited_cells(),41);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
+        assert_eq!(world.count_visited_cells(), 41);
+    }
+
+    #[test]
+    fn render_frames() {
+        let input = "..#.\n..^.\n....";
+        let parsed = Day6::parse(input).unwrap();
+        let mut frames = Vec::new();
+        Day6::render(&parsed, Some(2), &mut |canvas| {
+            frames.push(draw(canvas, false))
+        })
+        .unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], "..#.\n..^.\n....\n");
+        assert_eq!(frames[1], "..#.\n..X>\n....\n");
+        assert_eq!(frames[2], "..#.\n..XX\n....\n");
+    }
+
+    #[test]
+    fn endless_walk() {
+        let parsed = Day6::parse(".#..\n...#\n#^..\n..#.").unwrap();
+        let error = Day6::part1(&parsed).unwrap_err();
+        assert_eq!(error.to_string(), "the guard's walk never ends");
+    }
+
+    #[test]
+    fn parse_rendered_guard() {
+        let parsed = Day6::parse("#...\n....\nv..#").unwrap();
+        let mut frames = Vec::new();
+        Day6::render(&parsed, Some(1), &mut |canvas| {
+            frames.push(draw(canvas, false))
+        })
+        .unwrap();
+        assert!(frames[0].contains('v'));
+        assert_eq!(
+            WorldBuilder::build(&frames[0]).unwrap().guard.direction,
+            Down
+        );
+    }
+
+    #[test]
+    fn build_errors() {
+        let error = WorldBuilder::build("\n....\n.^x.\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+        assert_eq!(error.text, "x");
+        let error = WorldBuilder::build("....\n.#..").unwrap_err();
+        assert_eq!(error.message, "missing guard");
     }
 }
@@ -1,36 +1,23 @@
-use crate::day6_part2::State::Loop;
-use Direction::{Down, Left, Right, Up};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::position::Direction::{Down, Left, Right, Up};
+use crate::position::{Direction, Position};
 use std::cmp::PartialEq;
-use std::fs;
+use std::collections::HashSet;
 
-const FILE_NAME: &str = "input_day6.txt";
+const DAY: u8 = 6;
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut world = WorldBuilder::build(&input);
-    println!("{}",world.find_possible_loops())
-}
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-struct Position {
-    x: usize,
-    y: usize,
+    let world = WorldBuilder::build(input)?;
+    println!("{}", world.find_possible_loops());
+    Ok(())
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 enum Cell {
     NotVisited,
-    Visited(usize),
+    Visited,
     Obstruction,
     InitialGuardPosition(Direction),
 }
@@ -39,16 +26,15 @@ impl Cell {
     fn is_empty(&self) -> bool {
         match self {
             Cell::NotVisited => true,
-            Cell::Visited(_) => true,
+            Cell::Visited => true,
             Cell::InitialGuardPosition(_) => true,
             Cell::Obstruction => false,
         }
     }
     fn visit(&self) -> Cell {
         match self {
-            Cell::InitialGuardPosition(_) => self.clone(),
-            Cell::Visited(i) => Cell::Visited(i + 1),
-            Cell::NotVisited => Cell::Visited(1),
+            Cell::InitialGuardPosition(_) => *self,
+            Cell::Visited | Cell::NotVisited => Cell::Visited,
             Cell::Obstruction => panic!("can't visit obstruction!"),
         }
     }
@@ -61,25 +47,8 @@ struct Guard {
 }
 
 impl Guard {
-    fn next_position(&self) -> Result<Position, String> {
-        let mut next_position = self.position;
-        match self.direction {
-            Up => next_position.y += 1,
-            Right => next_position.x += 1,
-            Down => {
-                if next_position.y == 0 {
-                    return Err("Invalid position!".into());
-                }
-                next_position.y -= 1
-            }
-            Left => {
-                if next_position.x == 0 {
-                    return Err("Invalid position!".into());
-                }
-                next_position.x -= 1
-            }
-        }
-        Ok(next_position)
+    fn next_position(&self) -> Option<Position> {
+        self.position + self.direction
     }
 
     fn walk(&mut self) {
@@ -87,13 +56,7 @@ impl Guard {
     }
 
     fn rotate(&mut self) {
-        let new_direction = match self.direction {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        };
-        self.direction = new_direction;
+        self.direction = self.direction.clockwise();
     }
 }
 #[derive(Debug, PartialOrd, PartialEq, Clone)]
@@ -104,52 +67,37 @@ enum State {
 }
 
 #[derive(Debug, Clone)]
-struct World {
-    map: Vec<Vec<Cell>>,
+pub struct World {
+    map: Grid<Cell>,
     guard: Guard,
     state: State,
+    // where the guard turned and which way it faced after, a walk that repeats one never ends
+    turns: HashSet<(Position, Direction)>,
 }
 
 impl World {
-    fn get_size(&self) -> (usize,usize){
-      (self.map.len(), self.map[0].len())
-    }
-
     fn will_create_loop(&self, position: Position) -> bool {
         let mut simulation = self.clone();
         simulation.insert_cell(&position, Cell::Obstruction);
-        simulation.run(100_000);
-        if !simulation.is_done() {
-            panic!("timed out")
-        }
-        simulation.state == Loop
+        simulation.run();
+        simulation.state == State::Loop
     }
 
-    fn find_possible_loops(&self) -> usize {
-        let (rows, columns) = self.get_size();
-        let mut count = 0;
-        for y in 0..rows{
-            for x in 0..columns{
-                if self.will_create_loop(Position {x,y}){
-                    count +=1;
-                };
-            }
-        }
-
-        count
+    pub fn find_possible_loops(&self) -> usize {
+        self.map
+            .points()
+            .filter(|&point| self.will_create_loop(point.into()))
+            .count()
     }
 
-    fn run(&mut self, timeout: usize) {
-        let mut frame = 0;
-
-        while !self.is_done() && frame < timeout {
+    fn run(&mut self) {
+        while !self.is_done() {
             self.next_frame();
-            frame += 1;
         }
     }
 
     fn visit(&mut self, position: &Position) {
-        self.insert_cell(position, self.get_cell(position).unwrap().visit());
+        self.insert_cell(position, self.map[*position].visit());
     }
 
     fn is_done(&self) -> bool {
@@ -160,94 +108,74 @@ impl World {
         }
     }
 
-    fn get_cell(&self, position: &Position) -> Result<Cell, String> {
-        if position.y >= self.map.len() {
-            return Err("Row index out of bounds".into());
-        }
-        if position.x >= self.map[position.y].len() {
-            return Err("Column index out of bounds".into());
-        }
-        Ok(self.map[position.y][position.x])
-    }
     fn next_frame(&mut self) {
-        // println!("world: {:?}", self);
-
         if self.is_done() {
             return;
         }
 
-        let Ok(next_position) = &self.guard.next_position() else {
+        let Some(next_position) = self
+            .guard
+            .position
+            .step_within(self.guard.direction, self.map.size())
+        else {
             self.state = State::GuardExited;
             return;
         };
 
-        match self.get_cell(next_position) {
-            Err(_) => self.state = State::GuardExited,
-            Ok(Cell::Visited(i)) if i > 4 => self.state = State::Loop,
-            Ok(pos) => {
-                if pos.is_empty() {
-                    self.visit(&self.guard.next_position().unwrap());
-                    self.guard.walk()
-                } else {
-                    self.guard.rotate()
-                }
+        if self.map[next_position].is_empty() {
+            self.visit(&next_position);
+            self.guard.walk()
+        } else {
+            self.guard.rotate();
+            if !self
+                .turns
+                .insert((self.guard.position, self.guard.direction))
+            {
+                self.state = State::Loop
             }
         }
     }
 
     fn insert_cell(&mut self, position: &Position, cell: Cell) {
-        self.map[position.y][position.x] = cell;
+        self.map[*position] = cell;
     }
 }
-struct WorldBuilder();
+pub struct WorldBuilder();
 
 impl WorldBuilder {
-    fn build(input_raw: &str) -> World {
-        let input = input_raw.trim();
-        let map = Self::build_map(input);
-        let guard = Self::build_guard(&map);
-        World {
+    pub fn build(input: &str) -> Result<World, ParseError> {
+        let map = Grid::parse(DAY, input, Self::build_cell)?;
+        let guard = Self::build_guard(&map)
+            .ok_or_else(|| ParseError::new(DAY, input, "", "missing guard"))?;
+        Ok(World {
             map,
             guard,
             state: State::NotDone,
+            turns: HashSet::new(),
+        })
+    }
+
+    fn build_cell(c: char) -> Result<Cell, &'static str> {
+        match c {
+            '.' => Ok(Cell::NotVisited),
+            '^' => Ok(Cell::InitialGuardPosition(Up)),
+            '>' => Ok(Cell::InitialGuardPosition(Right)),
+            '<' => Ok(Cell::InitialGuardPosition(Left)),
+            'v' | 'V' => Ok(Cell::InitialGuardPosition(Down)),
+            '#' | 'O' => Ok(Cell::Obstruction),
+            _ => Err("invalid character"),
         }
     }
-
-    fn build_line(line: &str) -> Vec<Cell> {
-        line.trim().chars()
-            .map(|c| match c {
-                '.' => Cell::NotVisited,
-                '^' => Cell::InitialGuardPosition(Up),
-                '>' => Cell::InitialGuardPosition(Right),
-                '<' => Cell::InitialGuardPosition(Left),
-                'V' => Cell::InitialGuardPosition(Down),
-                '#' | 'O' => Cell::Obstruction,
-                _ => panic!("invalid character {c}!"),
-            })
-            .collect()
-    }
-    fn build_map(input: &str) -> Vec<Vec<Cell>> {
-        input.lines().rev().map(WorldBuilder::build_line).collect()
-    }
-    fn build_guard(map: &[Vec<Cell>]) -> Guard {
-        let (y, x) = map
-            .iter()
-            .enumerate()
-            .find_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .find(|(_, cell)| matches!(cell, Cell::InitialGuardPosition(_)))
-                    .map(|(x, _)| (y, x))
-            })
-            .unwrap();
-        let Cell::InitialGuardPosition(direction) = map[y][x] else {
+    fn build_guard(map: &Grid<Cell>) -> Option<Guard> {
+        let (x, y) = map.position(|cell| matches!(cell, Cell::InitialGuardPosition(_)))?;
+        let Cell::InitialGuardPosition(direction) = map[(x, y)] else {
             panic!("this shouldn't happen")
         };
 
-        Guard {
-            position: Position { x, y },
+        Some(Guard {
+            position: Position::new(x, y),
             direction,
-        }
+        })
     }
 }
 
@@ -261,11 +189,18 @@ pub mod tests {
 ####
 ....
 ";
-        let world = WorldBuilder::build(input.trim());
-        assert_eq!(world.guard.position, Position { x: 3, y: 2 });
+        let world = WorldBuilder::build(input.trim()).unwrap();
+        assert_eq!(world.guard.position, Position { x: 3, y: 0 });
         assert_eq!(world.guard.direction, Up);
-        assert!(!world.map[1][3].is_empty());
-        assert!(world.map[0][3].is_empty());
+        assert!(!world.map[(3, 1)].is_empty());
+        assert!(world.map[(3, 2)].is_empty());
+    }
+
+    #[test]
+    fn boxed_in_guard() {
+        let world = WorldBuilder::build(".#.\n#^.\n.#.").unwrap();
+        assert!(world.will_create_loop(Position::new(2, 1)));
+        assert!(!world.will_create_loop(Position::new(0, 0)));
     }
 
     #[test]
@@ -291,11 +226,11 @@ pub mod tests {
             direction: Up,
         };
         guard.walk();
-        assert_eq!(guard.position, Position { x: 2, y: 3 });
+        assert_eq!(guard.position, Position { x: 2, y: 1 });
         guard.rotate();
         guard.walk();
         guard.walk();
-        assert_eq!(guard.position, Position { x: 4, y: 3 });
+        assert_eq!(guard.position, Position { x: 4, y: 1 });
     }
     #[test]
     fn test_next_position() {
@@ -304,17 +239,17 @@ pub mod tests {
             direction: Up,
         };
         let next_pos = guard.next_position();
-        assert_eq!(next_pos, Ok(Position { x: 2, y: 3 }));
+        assert_eq!(next_pos, Some(Position { x: 2, y: 1 }));
         assert_eq!(guard.position, Position { x: 2, y: 2 });
         guard.rotate();
         let next_pos = guard.next_position();
-        assert_eq!(next_pos, Ok(Position { x: 3, y: 2 }));
+        assert_eq!(next_pos, Some(Position { x: 3, y: 2 }));
         assert_eq!(guard.position, Position { x: 2, y: 2 });
     }
     #[test]
     fn next_frame_walk() {
         let input = r"..>.";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert_eq!(world.guard.position, Position { x: 2, y: 0 });
 
         world.next_frame();
@@ -324,7 +259,7 @@ pub mod tests {
     #[test]
     fn next_frame_rotate() {
         let input = r"..>#";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert_eq!(world.guard.position, Position { x: 2, y: 0 });
         assert_eq!(world.guard.direction, Right);
 
@@ -337,7 +272,7 @@ pub mod tests {
     #[test]
     fn test_done() {
         let input = r"...>";
-        let mut world = WorldBuilder::build(input);
+        let mut world = WorldBuilder::build(input).unwrap();
         assert!(!world.is_done());
         world.next_frame();
         assert!(world.is_done());
@@ -346,16 +281,16 @@ pub mod tests {
     #[test]
     fn run_simulation() {
         let input = r">...";
-        let mut world = WorldBuilder::build(input);
-        world.run(5);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::GuardExited);
         assert_eq!(world.guard.position, Position { x: 3, y: 0 });
     }
     #[test]
     fn run_simulation_2() {
         let input = r">.#.";
-        let mut world = WorldBuilder::build(input);
-        world.run(5);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::GuardExited);
         assert_eq!(world.guard.position, Position { x: 1, y: 0 });
     }
@@ -365,8 +300,8 @@ pub mod tests {
         >.#.
         ....
         .#..";
-        let mut world = WorldBuilder::build(input);
-        world.run(10);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::GuardExited);
         assert_eq!(world.guard.position, Position { x: 0, y: 1 });
     }
@@ -384,8 +319,8 @@ pub mod tests {
         ........#.
         #.........
         ......#...";
-        let mut world = WorldBuilder::build(input);
-        world.run(1000);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::Loop)
     }
     #[test]
@@ -401,13 +336,13 @@ pub mod tests {
         ........#.
         #.........
         ......#O..";
-        let mut world = WorldBuilder::build(input);
-        world.run(1000);
+        let mut world = WorldBuilder::build(input).unwrap();
+        world.run();
         assert_eq!(world.state, State::Loop)
     }
-        #[test]
-        fn find_possible_loops() {
-            let input = r"
+    #[test]
+    fn find_possible_loops() {
+        let input = r"
         ....#.....
         .........#
         ..........
@@ -418,9 +353,9 @@ pub mod tests {
         ........#.
         #.........
         ......#...";
-            let mut world = WorldBuilder::build(input);
-            assert_eq!(world.find_possible_loops(), 6);
-        }
+        let world = WorldBuilder::build(input).unwrap();
+        assert_eq!(world.find_possible_loops(), 6);
+    }
     #[test]
     fn consider_obstruction_true() {
         let input = r"
@@ -434,9 +369,9 @@ pub mod tests {
         ........#.
         #.........
         ......#...";
-        let mut world = WorldBuilder::build(input);
-        let position = Position { x: 7, y: 0 };
-        assert_eq!(world.will_create_loop(position), true);
+        let world = WorldBuilder::build(input).unwrap();
+        let position = Position { x: 7, y: 9 };
+        assert!(world.will_create_loop(position));
     }
     #[test]
     fn consider_obstruction_true_2() {
@@ -451,9 +386,9 @@ pub mod tests {
         ........#.
         #.........
         ......#...";
-        let mut world = WorldBuilder::build(input);
-        let position = Position { x: 3, y: 3 };
-        assert_eq!(world.will_create_loop(position), true);
+        let world = WorldBuilder::build(input).unwrap();
+        let position = Position { x: 3, y: 6 };
+        assert!(world.will_create_loop(position));
     }
     #[test]
     fn consider_obstruction_false() {
@@ -468,8 +403,8 @@ pub mod tests {
         ........#.
         #.........
         ......#...";
-        let mut world = WorldBuilder::build(input);
-        let position = Position { x: 3, y: 4 };
-        assert_eq!(world.will_create_loop(position), false);
+        let world = WorldBuilder::build(input).unwrap();
+        let position = Position { x: 3, y: 5 };
+        assert!(!world.will_create_loop(position));
     }
 }
@@ -1,22 +1,45 @@
+use crate::answer::{self, Answer, CheckedIterator, Overflow, Unsolvable};
 use crate::day7::Operation::{Addition, Multiplication};
+use crate::day7_part2;
+use crate::error::ParseError;
+use crate::memo::Memo;
+use crate::scan::Pattern;
+use crate::solution::Solution;
 use itertools::Itertools;
-use std::collections::HashMap;
-use std::fs;
 
-const FILE_NAME: &str = "input_day7.txt";
+const DAY: u8 = 7;
 
-pub fn main() {
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(i64, Vec<i64>)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(equations: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(total_calibration(equations)?.into())
+    }
+    fn part2(equations: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(day7_part2::total_calibration(equations)?.into())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let input_parsed = parse_string(&input);
+    let input_parsed = parse_string(input)?;
+    let result = total_calibration(&input_parsed);
+    println!("{}", answer::show(result));
+    Ok(())
+}
+
+pub fn total_calibration(equations: &[(i64, Vec<i64>)]) -> Result<i64, Overflow> {
     let mut permutator = Permutator::new();
-    let result: i64 = input_parsed
+    equations
         .iter()
-        .filter(|&(result, parts)| could_possibly_be_true(*result, &parts, &mut permutator))
-        .map(|(result, _)| result)
-        .sum();
-    println!("{:?}",result);
+        .filter(|&(result, parts)| could_possibly_be_true(*result, parts, &mut permutator))
+        .map(|&(result, _)| result)
+        .checked_sum("total calibration")
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -26,19 +49,15 @@ enum Operation {
 }
 
 struct Permutator {
-    cache: HashMap<usize, Vec<Vec<Operation>>>,
+    cache: Memo<usize, Vec<Vec<Operation>>>,
 }
 impl Permutator {
     fn new() -> Permutator {
-        Permutator {
-            cache: HashMap::new(),
-        }
+        Permutator { cache: Memo::new() }
     }
-    fn all_permutation(&mut self, length: usize) -> Vec<Vec<Operation>> {
+    fn all_permutation(&mut self, length: usize) -> &[Vec<Operation>] {
         self.cache
-            .entry(length)
-            .or_insert(Self::calculate_permutations(length))
-            .to_owned()
+            .get_or_insert_with(length, |&length| Self::calculate_permutations(length))
     }
 
     fn calculate_permutations(length: usize) -> Vec<Vec<Operation>> {
@@ -49,44 +68,39 @@ impl Permutator {
     }
 }
 
-fn calculate_result(parts: &[i64], operations: &[Operation]) -> i64 {
-    let mut operations_iter = operations.iter();
-    parts
-        .iter()
-        .copied()
-        .reduce(|a, b| match operations_iter.next() {
-            None => {
-                panic!("this shouldn't happen!")
-            }
-            Some(Addition) => a + b,
-            Some(Multiplication) => a * b,
+// None when an intermediate value overflows, such a candidate can't match the result
+fn calculate_result(parts: &[i64], operations: &[Operation]) -> Option<i64> {
+    let (&first, rest) = parts
+        .split_first()
+        .expect("an equation has at least one part");
+    rest.iter()
+        .zip(operations)
+        .try_fold(first, |a, (&b, operation)| match operation {
+            Addition => a.checked_add(b),
+            Multiplication => a.checked_mul(b),
         })
-        .unwrap()
 }
 
-fn could_possibly_be_true(result: i64, parts: &[i64], mut permutator: &mut Permutator) -> bool {
+fn could_possibly_be_true(result: i64, parts: &[i64], permutator: &mut Permutator) -> bool {
     let permutations = permutator.all_permutation(parts.len());
     permutations
         .iter()
-        .any(|operations| calculate_result(parts, operations) == result)
+        .any(|operations| calculate_result(parts, operations) == Some(result))
 }
 
-fn parse_string(input: &str) -> Vec<(i64, Vec<i64>)> {
+pub fn parse_string(input: &str) -> Result<Vec<(i64, Vec<i64>)>, ParseError> {
+    let equation = Pattern::new(DAY, "{int}: {int+ }");
     input
         .lines()
-        .map(|line| line.split_once(':').unwrap())
-        .map(|(result, parts)| {
-            (
-                result.parse::<i64>().unwrap(),
-                parts
-                    .trim()
-                    .split_whitespace()
-                    .map(|num| num.parse::<i64>().unwrap())
-                    .collect(),
-            )
+        .map(|line| {
+            equation
+                .scan(line)
+                .map_err(|error| error.within(input, line))
         })
         .collect()
 }
+
+#[cfg(test)]
 pub mod tests {
     use super::*;
 
@@ -99,7 +113,8 @@ pub mod tests {
     }
     #[test]
     fn all_permutations_length_3() {
-        let permutations = Permutator::new().all_permutation(3);
+        let mut permutator = Permutator::new();
+        let permutations = permutator.all_permutation(3);
         assert_eq!(permutations.len(), 8);
         assert!(permutations.contains(&vec![Addition, Addition, Addition]));
         assert!(permutations.contains(&vec![Multiplication, Addition, Addition]));
@@ -114,6 +129,31 @@ pub mod tests {
     fn make_a_simple_calculation() {
         let parts = vec![1, 2, 3];
         let operations = vec![Addition, Multiplication];
-        assert_eq!(calculate_result(&parts, &operations), 9);
+        assert_eq!(calculate_result(&parts, &operations), Some(9));
+    }
+
+    #[test]
+    fn overflowing_candidate() {
+        let parts = vec![i64::MAX, 1];
+        assert_eq!(calculate_result(&parts, &[Addition]), None);
+        let mut permutator = Permutator::new();
+        assert!(could_possibly_be_true(i64::MAX, &parts, &mut permutator));
+        let equations = vec![(i64::MAX, parts), (1, vec![1])];
+        assert!(total_calibration(&equations).is_err());
+    }
+
+    #[test]
+    fn test_parse_string() {
+        let input = "190: 10 19\n3267: 81 40 27";
+        let expected = vec![(190, vec![10, 19]), (3267, vec![81, 40, 27])];
+        assert_eq!(parse_string(input), Ok(expected));
+        let error = parse_string("190: 10 19\n3267 81 40").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.message, "expected \":\"");
+        let error = parse_string("190: 10 19\n3267: 81 4o").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 10, "4o")
+        );
     }
 }
@@ -1,22 +1,25 @@
+use crate::answer::{self, CheckedIterator, Overflow};
+use crate::day7::parse_string;
 use crate::day7_part2::Operation::{Addition, Concatenation, Multiplication};
+use crate::error::ParseError;
+use crate::memo::Memo;
 use itertools::Itertools;
-use std::collections::HashMap;
-use std::fs;
 
-const FILE_NAME: &str = "input_day7.txt";
-
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let input_parsed = parse_string(&input);
+    let input_parsed = parse_string(input)?;
+    let result = total_calibration(&input_parsed);
+    println!("{}", answer::show(result));
+    Ok(())
+}
+
+pub fn total_calibration(equations: &[(i64, Vec<i64>)]) -> Result<i64, Overflow> {
     let mut permutator = Permutator::new();
-    let result: i64 = input_parsed
+    equations
         .iter()
-        .filter(|&(result, parts)| could_possibly_be_true(*result, &parts, &mut permutator))
-        .map(|(result, _)| result)
-        .sum();
-    println!("{:?}", result);
+        .filter(|&(result, parts)| could_possibly_be_true(*result, parts, &mut permutator))
+        .map(|&(result, _)| result)
+        .checked_sum("total calibration")
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -27,19 +30,15 @@ enum Operation {
 }
 
 struct Permutator {
-    cache: HashMap<usize, Vec<Vec<Operation>>>,
+    cache: Memo<usize, Vec<Vec<Operation>>>,
 }
 impl Permutator {
     fn new() -> Permutator {
-        Permutator {
-            cache: HashMap::new(),
-        }
+        Permutator { cache: Memo::new() }
     }
-    fn all_permutation(&mut self, length: usize) -> Vec<Vec<Operation>> {
+    fn all_permutation(&mut self, length: usize) -> &[Vec<Operation>] {
         self.cache
-            .entry(length)
-            .or_insert(Self::calculate_permutations(length))
-            .to_owned()
+            .get_or_insert_with(length, |&length| Self::calculate_permutations(length))
     }
 
     fn calculate_permutations(length: usize) -> Vec<Vec<Operation>> {
@@ -52,45 +51,28 @@ impl Permutator {
     }
 }
 
-fn calculate_result(parts: &[i64], operations: &[Operation]) -> i64 {
-    let mut operations_iter = operations.iter();
-    parts
-        .iter()
-        .copied()
-        .reduce(|a, b| match operations_iter.next() {
-            None => {
-                panic!("this shouldn't happen!")
-            }
-            Some(Addition) => a + b,
-            Some(Multiplication) => a * b,
-            Some(Concatenation) => format!("{}{}", a, b).parse().unwrap(),
+// None when an intermediate value overflows, such a candidate can't match the result
+fn calculate_result(parts: &[i64], operations: &[Operation]) -> Option<i64> {
+    let (&first, rest) = parts
+        .split_first()
+        .expect("an equation has at least one part");
+    rest.iter()
+        .zip(operations)
+        .try_fold(first, |a, (&b, operation)| match operation {
+            Addition => a.checked_add(b),
+            Multiplication => a.checked_mul(b),
+            Concatenation => format!("{a}{b}").parse().ok(),
         })
-        .unwrap()
 }
 
-fn could_possibly_be_true(result: i64, parts: &[i64], mut permutator: &mut Permutator) -> bool {
+fn could_possibly_be_true(result: i64, parts: &[i64], permutator: &mut Permutator) -> bool {
     let permutations = permutator.all_permutation(parts.len());
     permutations
         .iter()
-        .any(|operations| calculate_result(parts, operations) == result)
+        .any(|operations| calculate_result(parts, operations) == Some(result))
 }
 
-fn parse_string(input: &str) -> Vec<(i64, Vec<i64>)> {
-    input
-        .lines()
-        .map(|line| line.split_once(':').unwrap())
-        .map(|(result, parts)| {
-            (
-                result.parse::<i64>().unwrap(),
-                parts
-                    .trim()
-                    .split_whitespace()
-                    .map(|num| num.parse::<i64>().unwrap())
-                    .collect(),
-            )
-        })
-        .collect()
-}
+#[cfg(test)]
 pub mod tests {
     use super::*;
 
@@ -103,7 +85,8 @@ pub mod tests {
     }
     #[test]
     fn all_permutations_length_3() {
-        let permutations = Permutator::new().all_permutation(3);
+        let mut permutator = Permutator::new();
+        let permutations = permutator.all_permutation(3);
         assert_eq!(permutations.len(), 27);
         assert!(permutations.contains(&vec![Addition, Addition, Concatenation]));
         assert!(permutations.contains(&vec![Multiplication, Concatenation, Addition]));
@@ -118,6 +101,6 @@ pub mod tests {
     fn make_a_simple_calculation() {
         let parts = vec![1, 2, 3, 4];
         let operations = vec![Addition, Multiplication, Concatenation];
-        assert_eq!(calculate_result(&parts, &operations), 94);
+        assert_eq!(calculate_result(&parts, &operations), Some(94));
     }
 }
@@ -1,93 +1,126 @@
+use crate::answer::{Answer, Unsolvable};
+use crate::day8_part2;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::render::{self, Colour, Glyph, Render};
+use crate::solution::Solution;
+use crate::vector::Vector2;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::ops::{Add, Sub};
 
-const FILE_NAME: &str = "input_day8.txt";
+const DAY: u8 = 8;
 
-pub fn main() {
-    println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let result = count_antinodes(&input);
-    println!("{}", &result);
-}
+pub struct Day8;
 
-#[derive(PartialEq, Debug, Hash, Eq, Copy, Clone)]
-struct Point(i32, i32);
-impl Add for &Point {
-    type Output = Point;
+impl Solution for Day8 {
+    type Input = (
+        HashMap<char, Vec<Vector2<i32>>>,
+        (usize, usize), // width, height
+    );
 
-    fn add(self, other: Self) -> Self::Output {
-        Point(self.0 + other.0, self.1 + other.1)
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let grid = parse_grid(input)?;
+        Ok((find_antennas(&grid), (grid.width(), grid.height())))
+    }
+    fn part1((antennas, size): &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(find_all_antinodes(antennas, size).len().into())
+    }
+    fn part2((antennas, size): &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(day8_part2::find_all_antinodes(antennas, size).len().into())
     }
 }
-impl Sub for &Point {
-    type Output = Point;
 
-    fn sub(self, other: Self) -> Self::Output {
-        Point(self.0 - other.0, self.1 - other.1)
+impl Render for Day8 {
+    fn render(
+        input: &Self::Input,
+        _every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        frame(&antinodes_canvas(input));
+        Ok(())
     }
 }
 
-fn parse_string(input: &str) -> HashMap<char, Vec<Point>> {
-    input
-        .trim()
-        .lines()
-        .rev()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.trim()
-                .chars()
-                .enumerate()
-                .filter(|(_x, c)| c != &'.')
-                .map(move |(x, c)| (c, Point(x as i32, y as i32)))
-        })
-        .fold(HashMap::new(), |mut antennas, (c, point)| {
-            antennas.entry(c).or_default().push(point);
-            antennas
-        })
+// antinodes of part 1 as '#', an antenna that is also an antinode of another frequency keeps
+// its symbol on a red background
+fn antinodes_canvas((antennas, size): &<Day8 as Solution>::Input) -> Grid<Glyph> {
+    let mut canvas = Grid::filled(size.0, size.1, Glyph::new('.'));
+    for (index, &frequency) in antennas.keys().sorted().enumerate() {
+        for point in antennas[&frequency]
+            .iter()
+            .filter_map(|a| a.to_point_within(*size))
+        {
+            canvas[point] = Glyph::new(frequency).fg(render::palette(index));
+        }
+    }
+    for point in find_all_antinodes(antennas, size)
+        .into_iter()
+        .filter_map(|antinode| antinode.to_point_within(*size))
+    {
+        canvas[point] = match canvas[point].symbol {
+            '.' => Glyph::new('#').fg(Colour::Red),
+            _ => canvas[point].bg(Colour::Red),
+        };
+    }
+    canvas
 }
 
-fn get_size(input: &str) -> (usize, usize) {
-    let lines: Vec<_> = input.trim().lines().collect();
-    (lines.len(), lines[0].trim().len())
+pub fn main(input: &str) -> Result<(), ParseError> {
+    println!("this is main");
+    let result = count_antinodes(input)?;
+    println!("{}", &result);
+    Ok(())
+}
+
+pub fn parse_grid(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::chars(DAY, input)
+}
+
+pub fn find_antennas(grid: &Grid<char>) -> HashMap<char, Vec<Vector2<i32>>> {
+    grid.iter()
+        .filter(|(_, c)| **c != '.')
+        .fold(HashMap::new(), |mut antennas, ((x, y), c)| {
+            antennas
+                .entry(*c)
+                .or_default()
+                .push(Vector2::from_point((x, y)));
+            antennas
+        })
 }
 
 fn calculate_antinodes_for_frequency(
-    antennas: &Vec<Point>,
+    antennas: &Vec<Vector2<i32>>,
     size: &(usize, usize),
-) -> HashSet<Point> {
+) -> HashSet<Vector2<i32>> {
     let mut result = HashSet::new();
-    let size: (i32, i32) = (size.0 as i32, size.1 as i32);
 
     let pairs = antennas.iter().cartesian_product(antennas);
     for point_pair in pairs {
-        let distance = point_pair.0 - point_pair.1;
-        let possible_antinodes = [point_pair.0 + &distance, point_pair.1 - &distance];
+        let distance = *point_pair.0 - *point_pair.1;
+        let possible_antinodes = [*point_pair.0 + distance, *point_pair.1 - distance];
         let antinodes = possible_antinodes
             .iter()
             .filter(|p| !antennas.contains(p))
-            .filter(|&point| point.0 >= 0 && point.1 >= 0 && point.0 < size.0 && point.1 < size.1);
+            .filter(|point| point.to_point_within(*size).is_some());
         result.extend(antinodes);
     }
     result
 }
 
-fn find_all_antinodes(
-    antennas: &HashMap<char, Vec<Point>>,
+pub fn find_all_antinodes(
+    antennas: &HashMap<char, Vec<Vector2<i32>>>,
     size: &(usize, usize),
-) -> HashSet<Point> {
+) -> HashSet<Vector2<i32>> {
     antennas
-        .iter()
-        .flat_map(|(_, points)| calculate_antinodes_for_frequency(points, size))
+        .values()
+        .flat_map(|points| calculate_antinodes_for_frequency(points, size))
         .collect()
 }
 
-fn count_antinodes(input: &str) -> usize {
-    let size = get_size(input);
-    let antennas = parse_string(input);
-    find_all_antinodes(&antennas, &size).len()
+fn count_antinodes(input: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(input)?;
+    let size = (grid.width(), grid.height());
+    Ok(find_all_antinodes(&find_antennas(&grid), &size).len())
 }
 
 #[cfg(test)]
@@ -110,32 +143,76 @@ pub mod tests {
         ............
         ............";
 
-        let mut parsed = parse_string(input);
+        let parsed = find_antennas(&parse_grid(input).unwrap());
         println!("{:?}", parsed);
 
         let values_0 = parsed.get(&'0').unwrap();
-        let answer_0 = vec![Point(4, 7), Point(7, 8), Point(5, 9), Point(8, 10)];
+        let answer_0 = [
+            Vector2::new(8, 1),
+            Vector2::new(5, 2),
+            Vector2::new(7, 3),
+            Vector2::new(4, 4),
+        ];
         assert!(answer_0.iter().all(|p| values_0.iter().contains(p)));
         assert_eq!(values_0.len(), answer_0.len());
 
-        let values_A = parsed.get(&'A').unwrap();
-        let answer_A = vec![Point(9, 2), Point(8, 3), Point(6, 6)];
-        assert!(answer_A.iter().all(|p| values_A.iter().contains(p)));
-        assert_eq!(values_A.len(), answer_A.len());
+        let values_a = parsed.get(&'A').unwrap();
+        let answer_a = [Vector2::new(6, 5), Vector2::new(8, 8), Vector2::new(9, 9)];
+        assert!(answer_a.iter().all(|p| values_a.iter().contains(p)));
+        assert_eq!(values_a.len(), answer_a.len());
     }
 
     #[test]
     fn test_calculate_antinodes() {
-        let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
-        let a_antennas = vec![Point(1, 1), Point(2, 2)];
-        let b_antennas = vec![Point(1, 2), Point(3, 4), Point(4, 5)];
+        let mut antennas: HashMap<char, Vec<Vector2<i32>>> = HashMap::new();
+        let a_antennas = vec![Vector2::new(1, 1), Vector2::new(2, 2)];
+        let b_antennas = vec![Vector2::new(1, 2), Vector2::new(3, 4), Vector2::new(4, 5)];
         antennas.insert('a', a_antennas);
         antennas.insert('b', b_antennas);
         let size = (8, 8);
         let antinodes = find_all_antinodes(&antennas, &size);
-        let answers = HashSet::from([Point(0, 0), Point(3, 3), Point(2, 3), Point(5, 6)]);
+        let answers = HashSet::from([
+            Vector2::new(0, 0),
+            Vector2::new(3, 3),
+            Vector2::new(2, 3),
+            Vector2::new(5, 6),
+        ]);
         assert_eq!(antinodes, answers);
     }
+    #[test]
+    fn render_antinodes() {
+        let input = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+        let expected = "\
+......#....#
+...#....0...
+....#0....#.
+..#....0....
+....0....#..
+.#....A.....
+...#........
+#......#....
+........A...
+.........A..
+..........#.
+..........#.
+";
+        let canvas = antinodes_canvas(&Day8::parse(input).unwrap());
+        assert_eq!(render::draw(&canvas, false), expected);
+        assert_eq!(canvas[(6, 5)].background, Some(Colour::Red));
+    }
+
     #[test]
     fn test_count_antinodes() {
         let input = r"
@@ -151,6 +228,6 @@ pub mod tests {
         .........A..
         ............
         ............";
-        assert_eq!(count_antinodes(input), 14);
+        assert_eq!(count_antinodes(input).unwrap(), 14);
     }
 }
@@ -1,134 +1,80 @@
+use crate::day8::{find_antennas, parse_grid};
+use crate::error::ParseError;
+use crate::vector::Vector2;
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use std::ops::{Add, Sub};
 
-const FILE_NAME: &str = "input_day8.txt";
-
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let result = count_antinodes(&input);
+    let result = count_antinodes(input)?;
     println!("{}", &result);
-}
-
-#[derive(PartialEq, Debug, Hash, Eq, Copy, Clone)]
-struct Point(i32, i32);
-impl Sub for &Point {
-    type Output = (i32, i32);
-
-    fn sub(self, other: Self) -> Self::Output {
-        (self.0 - other.0, self.1 - other.1)
-    }
-}
-
-impl Point {
-    fn on_grid(&self, grid_size: &(usize, usize)) -> bool {
-        let grid_size = (grid_size.0 as i32, grid_size.1 as i32);
-        self.0 >= 0 && self.0 < grid_size.0 && self.1 >= 0 && self.1 < grid_size.1
-    }
+    Ok(())
 }
 
 #[derive(Debug)]
 struct ResonantHarmonic {
-    vector: (i32, i32),
+    vector: Vector2<i32>,
     grid_size: (usize, usize),
-    current: Point,
-    timeout: usize,
+    current: Vector2<i32>,
 }
 impl ResonantHarmonic {
     fn new(
-        init_point: &Point,
-        vector: &(i32, i32),
+        init_point: &Vector2<i32>,
+        vector: &Vector2<i32>,
         grid_size: &(usize, usize),
     ) -> ResonantHarmonic {
         ResonantHarmonic {
             vector: *vector,
             grid_size: *grid_size,
             current: *init_point,
-            timeout: 100,
         }
     }
 }
 
 impl Iterator for ResonantHarmonic {
-    type Item = Point;
+    type Item = Vector2<i32>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.vector == (0, 0) {
+        if self.vector == Vector2::default() {
             return None;
         }
 
-        let result = match self.current.on_grid(&self.grid_size) {
-            true => Some(self.current),
-            false => None,
-        };
-        self.current = Point(
-            self.current.0 + self.vector.0,
-            self.current.1 + self.vector.1,
-        );
-        self.timeout -= 1;
-        if self.timeout == 0 {
-            panic!("timeout reached!")
-        }
-
-        result
+        // the line never comes back once it left the grid
+        self.current.to_point_within(self.grid_size)?;
+        let result = self.current;
+        self.current += self.vector;
+        Some(result)
     }
 }
 
-fn parse_string(input: &str) -> HashMap<char, Vec<Point>> {
-    input
-        .trim()
-        .lines()
-        .rev()
-        .enumerate()
-        .flat_map(|(y, line)| {
-            line.trim()
-                .chars()
-                .enumerate()
-                .filter(|(_x, c)| c != &'.')
-                .map(move |(x, c)| (c, Point(x as i32, y as i32)))
-        })
-        .fold(HashMap::new(), |mut antennas, (c, point)| {
-            antennas.entry(c).or_default().push(point);
-            antennas
-        })
-}
-
-fn get_size(input: &str) -> (usize, usize) {
-    let lines: Vec<_> = input.trim().lines().collect();
-    (lines.len(), lines[0].trim().len())
-}
-
 fn calculate_antinodes_for_frequency(
-    antennas: &Vec<Point>,
+    antennas: &Vec<Vector2<i32>>,
     size: &(usize, usize),
-) -> HashSet<Point> {
+) -> HashSet<Vector2<i32>> {
     let mut result = HashSet::new();
 
     let pairs = antennas.iter().cartesian_product(antennas);
     for point_pair in pairs {
-        let distance = point_pair.0 - point_pair.1;
+        let distance = *point_pair.0 - *point_pair.1;
         result.extend(ResonantHarmonic::new(point_pair.0, &distance, size));
     }
     result
 }
 
-fn find_all_antinodes(
-    antennas: &HashMap<char, Vec<Point>>,
+pub fn find_all_antinodes(
+    antennas: &HashMap<char, Vec<Vector2<i32>>>,
     size: &(usize, usize),
-) -> HashSet<Point> {
+) -> HashSet<Vector2<i32>> {
     antennas
-        .iter()
-        .flat_map(|(_, points)| calculate_antinodes_for_frequency(points, size))
+        .values()
+        .flat_map(|points| calculate_antinodes_for_frequency(points, size))
         .collect()
 }
 
-fn count_antinodes(input: &str) -> usize {
-    let size = get_size(input);
-    let antennas = parse_string(input);
-    find_all_antinodes(&antennas, &size).len()
+fn count_antinodes(input: &str) -> Result<usize, ParseError> {
+    let grid = parse_grid(input)?;
+    let size = (grid.width(), grid.height());
+    Ok(find_all_antinodes(&find_antennas(&grid), &size).len())
 }
 
 #[cfg(test)]
@@ -151,38 +97,51 @@ pub mod tests {
         ............
         ............";
 
-        let parsed = parse_string(input);
+        let parsed = find_antennas(&parse_grid(input).unwrap());
 
         let values_0 = parsed.get(&'0').unwrap();
-        let answer_0 = vec![Point(4, 7), Point(7, 8), Point(5, 9), Point(8, 10)];
+        let answer_0 = [
+            Vector2::new(8, 1),
+            Vector2::new(5, 2),
+            Vector2::new(7, 3),
+            Vector2::new(4, 4),
+        ];
         assert!(answer_0.iter().all(|p| values_0.iter().contains(p)));
         assert_eq!(values_0.len(), answer_0.len());
 
         let values_a = parsed.get(&'A').unwrap();
-        let answer_a = vec![Point(9, 2), Point(8, 3), Point(6, 6)];
+        let answer_a = [Vector2::new(6, 5), Vector2::new(8, 8), Vector2::new(9, 9)];
         assert!(answer_a.iter().all(|p| values_a.iter().contains(p)));
         assert_eq!(values_a.len(), answer_a.len());
     }
 
     #[test]
     fn test_calculate_antinodes() {
-        let mut antennas: HashMap<char, Vec<Point>> = HashMap::new();
-        let a_antennas = vec![Point(1, 1), Point(2, 2)];
+        let mut antennas: HashMap<char, Vec<Vector2<i32>>> = HashMap::new();
+        let a_antennas = vec![Vector2::new(1, 1), Vector2::new(2, 2)];
         antennas.insert('a', a_antennas);
         let size = (8, 8);
         let antinodes = find_all_antinodes(&antennas, &size);
         let answers = HashSet::from([
-            Point(0, 0),
-            Point(1, 1),
-            Point(2, 2),
-            Point(3, 3),
-            Point(4, 4),
-            Point(5, 5),
-            Point(6, 6),
-            Point(7, 7),
+            Vector2::new(0, 0),
+            Vector2::new(1, 1),
+            Vector2::new(2, 2),
+            Vector2::new(3, 3),
+            Vector2::new(4, 4),
+            Vector2::new(5, 5),
+            Vector2::new(6, 6),
+            Vector2::new(7, 7),
         ]);
         assert_eq!(antinodes, answers);
     }
+    #[test]
+    fn wide_grid() {
+        let antennas = HashMap::from([('a', vec![Vector2::new(0, 0), Vector2::new(1, 0)])]);
+        let antinodes = find_all_antinodes(&antennas, &(300, 1));
+        assert_eq!(antinodes.len(), 300);
+        assert!(antinodes.contains(&Vector2::new(299, 0)));
+    }
+
     #[test]
     fn test_count_antinodes() {
         let input = r"
@@ -198,14 +157,19 @@ pub mod tests {
         .........A..
         ............
         ............";
-        assert_eq!(count_antinodes(input), 34);
+        assert_eq!(count_antinodes(input).unwrap(), 34);
     }
 
     #[test]
     fn resonant_harmonic() {
         let harmonic_series: Vec<_> =
-            ResonantHarmonic::new(&Point(0, 0), &(2, 1), &(8, 8)).collect();
-        let answer = [Point(0, 0), Point(2, 1), Point(4, 2), Point(6, 3)];
+            ResonantHarmonic::new(&Vector2::new(0, 0), &Vector2::new(2, 1), &(8, 8)).collect();
+        let answer = [
+            Vector2::new(0, 0),
+            Vector2::new(2, 1),
+            Vector2::new(4, 2),
+            Vector2::new(6, 3),
+        ];
         assert_eq!(harmonic_series, answer)
     }
 }
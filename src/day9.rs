@@ -1,21 +1,59 @@
-use itertools::{Itertools, concat};
-use std::fs;
+use crate::answer::{self, Answer, Overflow, Unsolvable};
+use crate::day9_part2::Disk;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::render::{self, Glyph, Render};
+use crate::solution::Solution;
+use itertools::Itertools;
 use std::iter::once;
-use std::thread::yield_now;
 
-const FILE_NAME: &str = "input_day9.txt";
 const EMPTY_SPACE: i32 = -1;
+const DAY: u8 = 9;
 
-pub fn main() {
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    fn part1(disk: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok(check_sum(&unite_free_space(disk))?.into())
+    }
+    fn part2(disk: &Self::Input) -> Result<Answer, Unsolvable> {
+        let mut disk = Disk::from_space(disk.clone());
+        disk.order();
+        Ok(disk.checksum()?.into())
+    }
+}
+
+impl Render for Day9 {
+    fn render(
+        disk: &Self::Input,
+        every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String> {
+        let mut disk = Disk::from_space(disk.clone());
+        disk.order_observed(|step, disk| {
+            if render::is_frame(every, step) {
+                frame(&disk.canvas())
+            }
+        });
+        frame(&disk.canvas());
+        Ok(())
+    }
+}
+
+pub fn main(input_raw: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input_raw = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let input = parse_string(&input_raw);
+    let input = parse_string(input_raw)?;
     let disk_sorted = unite_free_space(&input);
-    print!("{}", check_sum(&disk_sorted));
+    print!("{}", answer::show(check_sum(&disk_sorted)));
+    Ok(())
 }
 
-fn unite_free_space(disk: &[i32]) -> Vec<i32> {
+pub fn unite_free_space(disk: &[i32]) -> Vec<i32> {
     let mut free_space_index = 0;
     let mut block_index = disk.len().saturating_sub(1);
     let mut disk = disk.to_owned();
@@ -36,24 +74,34 @@ fn is_free_space(item: i32) -> bool {
     item == EMPTY_SPACE
 }
 
-fn check_sum(disk: &[i32]) -> usize {
+pub fn check_sum(disk: &[i32]) -> Result<usize, Overflow> {
     disk.iter()
         .enumerate()
-        .map(|(index, content)| match is_free_space(*content) {
-            true => 0,
-            false => index * (*content as usize),
+        .filter(|&(_, content)| !is_free_space(*content))
+        .try_fold(0, |total, (index, content)| {
+            let block = answer::mul(index, *content as usize, "checksum")?;
+            answer::add(total, block, "checksum")
         })
-        .sum()
 }
 
-fn parse_string(s: &str) -> Vec<i32> {
-    let pairs: Vec<(i32, i32)> = s
-        .chars()
-        .map(|c| c.to_digit(10).unwrap() as i32)
+pub fn parse_digits(s: &str) -> Result<Vec<i32>, ParseError> {
+    s.trim_end()
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|digit| digit as i32)
+                .ok_or_else(|| ParseError::new(DAY, s, &s[i..i + c.len_utf8()], "invalid digit"))
+        })
+        .collect()
+}
+
+pub fn parse_string(s: &str) -> Result<Vec<i32>, ParseError> {
+    let pairs: Vec<(i32, i32)> = parse_digits(s)?
+        .into_iter()
         .chain(once(0)) // to avoid the last item being dropped if odd
         .tuples()
         .collect();
-    pairs
+    Ok(pairs
         .iter()
         .enumerate()
         .flat_map(|(index, (block_size, free_space))| {
@@ -63,7 +111,7 @@ fn parse_string(s: &str) -> Vec<i32> {
             ]
             .concat()
         })
-        .collect()
+        .collect())
 }
 
 #[cfg(test)]
@@ -74,7 +122,7 @@ pub mod tests {
     fn test_parse_string() {
         let string = "143023";
         let parsed = vec![0, -1, -1, -1, -1, 1, 1, 1, 2, 2, -1, -1, -1];
-        assert_eq!(parse_string(string), parsed);
+        assert_eq!(parse_string(string), Ok(parsed));
     }
 
     #[test]
@@ -91,7 +139,7 @@ pub mod tests {
     #[test]
     fn test_check_sum() {
         let s = parse_for_testing("0099811188827773336446555566..............");
-        assert_eq!(check_sum(&s), 1928);
+        assert_eq!(check_sum(&s), Ok(1928));
     }
     fn parse_for_testing(s: &str) -> Vec<i32> {
         s.chars()
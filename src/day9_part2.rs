@@ -1,38 +1,47 @@
-const FILE_NAME: &str = "input_day9.txt";
 const EMPTY_SPACE: i32 = -1;
 
+use crate::answer::{self, Overflow};
+use crate::day9::parse_digits;
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::render::{self, Glyph};
 use itertools::Itertools;
-use std::fs;
 use std::iter::once;
 
-struct Disk {
+// blocks per row when the disk is drawn
+const ROW_LENGTH: usize = 100;
+
+pub struct Disk {
     space: Vec<i32>,
 }
 
-
 impl Disk {
-    fn order(&mut self) {
+    pub fn order(&mut self) {
+        self.order_observed(|_, _| {})
+    }
+
+    // `observe` sees the disk before every file is moved, files go from the highest id down
+    pub fn order_observed(&mut self, mut observe: impl FnMut(usize, &Disk)) {
         let raw_chunks = self
             .space
             .clone()
             .into_iter()
             .enumerate()
-            .filter(|(idx, value)| *value != EMPTY_SPACE)
-            .chunk_by(|(idx, value)| *value);
+            .filter(|(_, value)| *value != EMPTY_SPACE)
+            .chunk_by(|(_, value)| *value);
         let chunks: Vec<_> = raw_chunks
             .into_iter()
             .map(|(key, chunk)| (key, chunk.collect_vec()))
             .collect();
-        for (_, chunk) in chunks.into_iter().rev() {
+        for (step, (_, chunk)) in chunks.into_iter().rev().enumerate() {
+            observe(step, self);
             let ((block_idx, _), block_size) = (chunk[0], chunk.len());
             let free_space = self.find_empty_space(block_size);
             match free_space {
-                None => {}
-                Some(free_space_idx) => {
-                    if free_space_idx < block_idx {
-                        self.swap_blocks(block_idx, free_space_idx, block_size);
-                    }
+                Some(free_space_idx) if free_space_idx < block_idx => {
+                    self.swap_blocks(block_idx, free_space_idx, block_size);
                 }
+                _ => {}
             }
         }
     }
@@ -51,29 +60,48 @@ impl Disk {
         source_chunk.swap_with_slice(target_chunk);
     }
 
-    fn checksum(&self) -> usize {
+    pub fn checksum(&self) -> Result<usize, Overflow> {
         self.space
             .iter()
             .enumerate()
-            .filter(|&(_, &block_id)| {block_id!=EMPTY_SPACE})
-            .map(|(pos, &block_id)| pos * block_id as usize)
-            .sum()
+            .filter(|&(_, &block_id)| block_id != EMPTY_SPACE)
+            .try_fold(0, |total, (pos, &block_id)| {
+                let block = answer::mul(pos, block_id as usize, "checksum")?;
+                answer::add(total, block, "checksum")
+            })
     }
 
-    fn new(input: &str) -> Disk {
-        Disk {
-            space: Disk::parse_string(input),
+    // the blocks wrapped in rows, a file shows the last digit of its id
+    pub fn canvas(&self) -> Grid<Glyph> {
+        let height = self.space.len().div_ceil(ROW_LENGTH);
+        let mut canvas = Grid::filled(ROW_LENGTH, height, Glyph::new(' '));
+        for (index, &block_id) in self.space.iter().enumerate() {
+            canvas[(index % ROW_LENGTH, index / ROW_LENGTH)] = match block_id {
+                EMPTY_SPACE => Glyph::new('.'),
+                id => {
+                    let digit = char::from_digit(id as u32 % 10, 10).unwrap();
+                    Glyph::new(digit).fg(render::palette(id as usize))
+                }
+            };
         }
+        canvas
+    }
+
+    pub fn new(input: &str) -> Result<Disk, ParseError> {
+        Ok(Disk::from_space(Disk::parse_string(input)?))
     }
 
-    fn parse_string(s: &str) -> Vec<i32> {
-        let pairs: Vec<(i32, i32)> = s
-            .chars()
-            .map(|c| c.to_digit(10).unwrap() as i32)
+    pub fn from_space(space: Vec<i32>) -> Disk {
+        Disk { space }
+    }
+
+    fn parse_string(s: &str) -> Result<Vec<i32>, ParseError> {
+        let pairs: Vec<(i32, i32)> = parse_digits(s)?
+            .into_iter()
             .chain(once(0)) // to avoid the last item being dropped if odd
             .tuples()
             .collect();
-        pairs
+        Ok(pairs
             .iter()
             .enumerate()
             .flat_map(|(index, (block_size, free_space))| {
@@ -83,18 +111,17 @@ impl Disk {
                 ]
                 .concat()
             })
-            .collect()
+            .collect())
     }
 }
 
-pub fn main() {
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
-    let mut disk = Disk::new(&input);
+    let mut disk = Disk::new(input)?;
     disk.order();
     let result = disk.checksum();
-    println!("{}",result);
+    println!("{}", answer::show(result));
+    Ok(())
 }
 
 #[cfg(test)]
@@ -104,7 +131,7 @@ pub mod tests {
     fn test_parse_string() {
         let string = "143023";
         let expected = vec![0, -1, -1, -1, -1, 1, 1, 1, 2, 2, -1, -1, -1];
-        let result = Disk::new(string).space;
+        let result = Disk::new(string).unwrap().space;
         assert_eq!(expected, result);
 
         let string = "2333133121414131402";
@@ -112,7 +139,7 @@ pub mod tests {
             0, 0, -1, -1, -1, 1, 1, 1, -1, -1, -1, 2, -1, -1, -1, 3, 3, 3, -1, 4, 4, -1, 5, 5, 5,
             5, -1, 6, 6, 6, 6, -1, 7, 7, 7, -1, 8, 8, 8, 8, 9, 9,
         ];
-        let result = Disk::new(string).space;
+        let result = Disk::new(string).unwrap().space;
         assert_eq!(expected, result);
     }
 
@@ -124,7 +151,7 @@ pub mod tests {
             0, 0, 9, 9, 2, 1, 1, 1, 7, 7, 7, -1, 4, 4, -1, 3, 3, 3, -1, -1, -1, -1, 5, 5, 5, 5, -1,
             6, 6, 6, 6, -1, -1, -1, -1, -1, 8, 8, 8, 8, -1, -1,
         ];
-        let mut disk = Disk::new(string);
+        let mut disk = Disk::new(string).unwrap();
         disk.order();
         let result = disk.space;
         assert_eq!(expected, result);
@@ -133,22 +160,23 @@ pub mod tests {
     fn checksum_disk() {
         let string = "2333133121414131402";
         // 00...111...2...333.44.5555.6666.777.888899
-        let expected = vec![
+        let expected_space = vec![
             0, 0, 9, 9, 2, 1, 1, 1, 7, 7, 7, -1, 4, 4, -1, 3, 3, 3, -1, -1, -1, -1, 5, 5, 5, 5, -1,
             6, 6, 6, 6, -1, -1, -1, -1, -1, 8, 8, 8, 8, -1, -1,
         ];
-        let mut disk = Disk::new(string);
+        let mut disk = Disk::new(string).unwrap();
         disk.order();
+        assert_eq!(disk.space, expected_space);
         let result = disk.checksum();
         let expected = 2858;
-        assert_eq!(result, expected);
+        assert_eq!(result, Ok(expected));
     }
 
     #[test]
     fn test_find_empty_space() {
         let string = "2333133121414131402";
         // 00...111...2...333.44.5555.6666.777.888899
-        let disk = Disk::new(string);
+        let disk = Disk::new(string).unwrap();
         let empty_space_index = disk.find_empty_space(3);
         assert_eq!(empty_space_index, Some(2));
         let empty_space_index = disk.find_empty_space(1);
@@ -161,17 +189,32 @@ pub mod tests {
     fn test_swap_blocks() {
         let string = "2333133121414131402";
         // 00...111...2...333.44.5555.6666.777.888899
-        let mut disk = Disk::new(string);
+        let mut disk = Disk::new(string).unwrap();
         disk.swap_blocks(5, 2, 3);
         assert_eq!(disk.space[2..5], [1, 1, 1]);
         assert_eq!(disk.space[6..9], [-1, -1, -1]);
     }
 
+    #[test]
+    fn observe_order() {
+        let mut disk = Disk::new("2333133121414131402").unwrap();
+        let mut steps = Vec::new();
+        disk.order_observed(|step, disk| steps.push((step, disk.space[2])));
+        assert_eq!(steps.len(), 10);
+        assert_eq!(steps[0], (0, EMPTY_SPACE));
+        assert_eq!(steps[1], (1, 9));
+        let text = render::draw(&disk.canvas(), false);
+        assert_eq!(
+            text.trim_end(),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+    }
+
     // #[test]
     // fn check_grpup_by(){
     //     let string = "2333133121414131402";
     //     // 00...111...2...333.44.5555.6666.777.888899
-    //     let mut disk = Disk::new(string);
+    //     let mut disk = Disk::new(string).unwrap();
     //     for (i,g) in &disk.space.iter().chunk_by(|x| *x){
     //         println!("{:?}, {}", i, g.count());
     //     }
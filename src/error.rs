@@ -0,0 +1,105 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    // `fragment` has to be a slice of `source`, positions are 1-based
+    pub fn new(day: u8, source: &str, fragment: &str, message: impl Into<String>) -> ParseError {
+        let (line, column) = locate(source, fragment);
+        ParseError {
+            day,
+            line,
+            column,
+            text: fragment.to_owned(),
+            message: message.into(),
+        }
+    }
+
+    // moves an error found in `inner` to its position in `outer`
+    pub fn within(self, outer: &str, inner: &str) -> ParseError {
+        let (line, column) = locate(outer, inner);
+        ParseError {
+            column: match self.line {
+                1 => self.column + column - 1,
+                _ => self.column,
+            },
+            line: self.line + line - 1,
+            ..self
+        }
+    }
+}
+
+fn locate(source: &str, fragment: &str) -> (usize, usize) {
+    let offset = (fragment.as_ptr() as usize).wrapping_sub(source.as_ptr() as usize);
+    if offset > source.len() || !source.is_char_boundary(offset) {
+        return (1, 1);
+    }
+    let before = &source[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " {:?}", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_fragment() {
+        let input = "1 2\n3 x4\n5 6";
+        let fragment = &input[6..8];
+        let error = ParseError::new(2, input, fragment, "invalid number");
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.text, "x4");
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 2, column 3: invalid number \"x4\""
+        );
+    }
+
+    #[test]
+    fn error_within_outer_input() {
+        let input = "rules\n\n  a b\n  c d";
+        let block = &input[7..];
+        let line = block.lines().nth(1).unwrap();
+        let error = ParseError::new(5, line, &line[4..], "bad").within(input, line);
+        assert_eq!((error.line, error.column), (4, 5));
+        let first_line = block.lines().next().unwrap();
+        let error = ParseError::new(5, block, &first_line[2..3], "bad").within(input, block);
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn unrelated_fragment() {
+        let other = String::from("def");
+        let error = ParseError::new(1, "abc", &other, "missing");
+        assert_eq!((error.line, error.column), (1, 1));
+    }
+}
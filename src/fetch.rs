@@ -0,0 +1,324 @@
+use std::env;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2024;
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+pub const SESSION_FILE: &str = ".aoc_session";
+// the site asks for a user agent that says where the requests come from
+const USER_AGENT: &str = "day1q1 input fetcher";
+// the least time between two downloads, also across runs
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+const STAMP_FILE: &str = ".last_fetch";
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+pub trait Http {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, String>;
+}
+
+// the real site only speaks https, which std can't, so this one runs curl
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurlHttp;
+
+impl Http for CurlHttp {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, String> {
+        // the headers go through stdin so the session token doesn't show up in the process list
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--write-out", "\n%{http_code}"])
+            .args(["--header", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| format!("can't run curl: {error}"))?;
+        let headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}\n"))
+            .collect();
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(headers.as_bytes())
+                .map_err(|error| format!("can't pass the headers to curl: {error}"))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|error| format!("can't run curl: {error}"))?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("curl failed: {}", error.trim()));
+        }
+        let output = String::from_utf8(output.stdout)
+            .map_err(|_| format!("{url} didn't answer with UTF-8"))?;
+        let (body, status) = output
+            .rsplit_once('\n')
+            .ok_or(format!("curl didn't report a status for {url}"))?;
+        let status = status
+            .parse()
+            .map_err(|_| format!("curl reported an invalid status {status:?}"))?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_owned(),
+        })
+    }
+}
+
+// plain http/1.1 over a socket, enough for a local stand-in of the site
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpHttp;
+
+impl Http for TcpHttp {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<HttpResponse, String> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or(format!("{url} isn't a plain http url"))?;
+        let (host, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+        let path = if path.is_empty() { "/" } else { path };
+        let mut stream = TcpStream::connect(host)
+            .map_err(|error| format!("can't connect to {host}: {error}"))?;
+        let mut request = format!("GET {path} HTTP/1.1\r\nHost: {host}\r\n");
+        for (name, value) in headers {
+            request.push_str(&format!("{name}: {value}\r\n"));
+        }
+        request.push_str("Connection: close\r\n\r\n");
+        let mut response = String::new();
+        stream
+            .write_all(request.as_bytes())
+            .and_then(|_| stream.read_to_string(&mut response))
+            .map_err(|error| format!("can't get {url}: {error}"))?;
+
+        let (head, body) = response
+            .split_once("\r\n\r\n")
+            .ok_or(format!("{url} answered without headers"))?;
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or(format!("{url} answered without a status"))?;
+        Ok(HttpResponse {
+            status,
+            body: body.to_owned(),
+        })
+    }
+}
+
+// the token from $AOC_SESSION, otherwise from the file, with or without the cookie name
+pub fn session(file: &Path) -> Result<String, String> {
+    let token = match env::var(SESSION_VARIABLE) {
+        Ok(token) => token,
+        Err(_) => fs::read_to_string(file).map_err(|error| {
+            format!(
+                "no session token, set ${SESSION_VARIABLE} or write it to {}: {error}",
+                file.display()
+            )
+        })?,
+    };
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() {
+        return Err("the session token is empty".into());
+    }
+    Ok(token.to_owned())
+}
+
+// keeps downloads `interval` apart, the time of the last one is the modification time of
+// a stamp file so separate runs see it too
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    stamp: PathBuf,
+    interval: Duration,
+}
+
+impl Throttle {
+    pub fn new(dir: &Path, interval: Duration) -> Throttle {
+        Throttle {
+            stamp: dir.join(STAMP_FILE),
+            interval,
+        }
+    }
+
+    pub fn wait(&self) -> Result<(), String> {
+        let elapsed = fs::metadata(&self.stamp)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        if let Some(elapsed) = elapsed
+            && elapsed < self.interval
+        {
+            thread::sleep(self.interval - elapsed);
+        }
+        fs::write(&self.stamp, "")
+            .map_err(|error| format!("can't write {}: {error}", self.stamp.display()))
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+#[derive(Debug)]
+pub struct Fetcher<H: Http> {
+    pub http: H,
+    pub base_url: String,
+    pub session: String,
+    pub throttle: Throttle,
+}
+
+impl<H: Http> Fetcher<H> {
+    // an input that is already at `path` is never downloaded again
+    pub fn fetch(&self, day: u8, path: &Path) -> Result<Fetched, String> {
+        if path.exists() {
+            return Ok(Fetched::Cached);
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|error| format!("can't create {}: {error}", dir.display()))?;
+        }
+        self.throttle.wait()?;
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let cookie = format!("session={}", self.session);
+        let response = self
+            .http
+            .get(&url, &[("Cookie", &cookie), ("User-Agent", USER_AGENT)])?;
+        match response.status {
+            200 if response.body.is_empty() => return Err(format!("{url} returned no input")),
+            200 => {}
+            404 => return Err(format!("day {day} isn't unlocked yet")),
+            400 | 401 | 403 => {
+                return Err(format!(
+                    "{url} refused the session token ({}), it may have expired",
+                    response.status
+                ));
+            }
+            status => return Err(format!("{url} answered {status}")),
+        }
+        // a partial download never looks like a cached input
+        let partial = path.with_extension("part");
+        fs::write(&partial, &response.body)
+            .and_then(|_| fs::rename(&partial, path))
+            .map_err(|error| format!("can't write {}: {error}", path.display()))?;
+        Ok(Fetched::Downloaded)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::process;
+    use std::sync::mpsc::{self, Receiver};
+    use std::time::Instant;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("day1q1-fetch-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // a stand-in for the site, answers `responses` in order and passes on the request heads
+    fn stand_in(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                while reader.read_line(&mut head).unwrap() > 2 {}
+                sender.send(head).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (base_url, receiver)
+    }
+
+    fn fetcher(base_url: String, dir: &Path, interval: Duration) -> Fetcher<TcpHttp> {
+        Fetcher {
+            http: TcpHttp,
+            base_url,
+            session: "abc123".into(),
+            throttle: Throttle::new(dir, interval),
+        }
+    }
+
+    #[test]
+    fn downloads_then_caches() {
+        let dir = temp_dir("cache");
+        let (base_url, requests) = stand_in(vec![(200, "1 2\n3 4\n")]);
+        let fetcher = fetcher(base_url, &dir, Duration::ZERO);
+        let path = dir.join("input_day3.txt");
+        assert_eq!(fetcher.fetch(3, &path), Ok(Fetched::Downloaded));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+        let head = requests.recv().unwrap();
+        assert!(head.starts_with("GET /2024/day/3/input HTTP/1.1\r\n"));
+        assert!(head.contains("Cookie: session=abc123\r\n"));
+        assert!(head.contains("User-Agent: "));
+        // the stand-in only answers once, a second request would fail
+        assert_eq!(fetcher.fetch(3, &path), Ok(Fetched::Cached));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let dir = temp_dir("errors");
+        let (base_url, _requests) = stand_in(vec![
+            (404, "Please don't repeatedly request this endpoint"),
+            (400, "Puzzle inputs differ by user."),
+            (200, ""),
+        ]);
+        let fetcher = fetcher(base_url, &dir, Duration::ZERO);
+        let path = dir.join("input_day25.txt");
+        assert!(fetcher.fetch(25, &path).unwrap_err().contains("unlocked"));
+        assert!(fetcher.fetch(25, &path).unwrap_err().contains("session"));
+        assert!(fetcher.fetch(25, &path).unwrap_err().contains("no input"));
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn throttles_downloads() {
+        let dir = temp_dir("throttle");
+        let (base_url, _requests) = stand_in(vec![(200, "a"), (200, "b")]);
+        let interval = Duration::from_millis(300);
+        let fetcher = fetcher(base_url, &dir, interval);
+        let start = Instant::now();
+        fetcher.fetch(1, &dir.join("input_day1.txt")).unwrap();
+        fetcher.fetch(2, &dir.join("input_day2.txt")).unwrap();
+        assert!(start.elapsed() >= interval);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn session_from_file() {
+        if env::var(SESSION_VARIABLE).is_ok() {
+            return;
+        }
+        let dir = temp_dir("session");
+        let file = dir.join(SESSION_FILE);
+        assert!(session(&file).unwrap_err().contains(SESSION_VARIABLE));
+        fs::write(&file, "session=abc123\n").unwrap();
+        assert_eq!(session(&file), Ok("abc123".to_string()));
+        fs::write(&file, " \n").unwrap();
+        assert!(session(&file).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
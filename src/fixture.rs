@@ -0,0 +1,197 @@
+use crate::check::Outcome;
+use crate::runner;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const FIXTURE_DIR: &str = "artifacts/test_files";
+pub const EXAMPLE_PREFIX: &str = "example";
+pub const ANSWERS_EXTENSION: &str = "answers";
+
+pub fn day_dir(day: u8) -> String {
+    format!("{FIXTURE_DIR}/day{day}")
+}
+
+// an example input dayN/exampleK.txt with its expected answers in dayN/exampleK.answers
+#[derive(Debug, PartialEq)]
+pub struct Fixture {
+    pub day: u8,
+    pub path: PathBuf,
+    pub answers: BTreeMap<u8, String>,
+}
+
+impl Fixture {
+    pub fn load(day: u8, path: PathBuf) -> Result<Fixture, String> {
+        let sidecar = path.with_extension(ANSWERS_EXTENSION);
+        let answers = parse_answers(&read(&sidecar)?)
+            .map_err(|error| format!("{}: {error}", sidecar.display()))?;
+        Ok(Fixture { day, path, answers })
+    }
+
+    // runs every part with a recorded answer through the day's solver
+    pub fn check(&self) -> Result<Vec<(u8, Outcome)>, String> {
+        let entry = runner::find_day(self.day)?;
+        let input = read(&self.path)?;
+        self.answers
+            .iter()
+            .map(|(&part, expected)| {
+                let actual = (entry.solve)(&input, part)?;
+                Ok((part, Outcome::compare(Some(expected), actual)))
+            })
+            .collect()
+    }
+}
+
+// one "<part> <answer>" per line, lines starting with # are comments
+pub fn parse_answers(text: &str) -> Result<BTreeMap<u8, String>, String> {
+    let mut answers = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("invalid answer on line {}: {line:?}", index + 1);
+        let (part, answer) = line.split_once(' ').ok_or_else(invalid)?;
+        let part = part.parse().map_err(|_| invalid())?;
+        answers.insert(part, answer.trim().to_owned());
+    }
+    Ok(answers)
+}
+
+// every dayN/exampleK.txt below `dir`, ordered by day and file name
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>, String> {
+    let mut fixtures = Vec::new();
+    for day_dir in list(dir)? {
+        let Some(day) = file_name(&day_dir)
+            .strip_prefix("day")
+            .and_then(|day| day.parse().ok())
+        else {
+            continue;
+        };
+        for path in list(&day_dir)? {
+            let is_example = file_name(&path).starts_with(EXAMPLE_PREFIX)
+                && path.extension().is_some_and(|extension| extension == "txt");
+            if is_example {
+                fixtures.push(Fixture::load(day, path)?);
+            }
+        }
+    }
+    fixtures.sort_by(|a, b| (a.day, &a.path).cmp(&(b.day, &b.path)));
+    Ok(fixtures)
+}
+
+// checks every fixture of `day`, the error lists each failing part
+pub fn check_day(day: u8) -> Result<(), String> {
+    let fixtures: Vec<Fixture> = discover(Path::new(FIXTURE_DIR))?
+        .into_iter()
+        .filter(|fixture| fixture.day == day)
+        .collect();
+    if fixtures.is_empty() {
+        return Err(format!("no examples in {}", day_dir(day)));
+    }
+    check_all(&fixtures)
+}
+
+pub fn check_all(fixtures: &[Fixture]) -> Result<(), String> {
+    let mut failures = Vec::new();
+    for fixture in fixtures {
+        match fixture.check() {
+            Ok(outcomes) => failures.extend(
+                outcomes
+                    .into_iter()
+                    .filter(|(_, outcome)| !matches!(outcome, Outcome::Pass(_)))
+                    .map(|(part, outcome)| {
+                        format!("{} part {part}: {outcome}", fixture.path.display())
+                    }),
+            ),
+            Err(error) => failures.push(format!("{}: {error}", fixture.path.display())),
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("can't read {}: {error}", path.display()))
+}
+
+fn list(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect()
+        })
+        .map_err(|error| format!("can't list {}: {error}", dir.display()))
+}
+
+fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::runner::DAYS;
+
+    #[test]
+    fn parse_sidecar() {
+        let answers = parse_answers("# part answer\n1 161\n\n2 48\n").unwrap();
+        assert_eq!(answers.get(&1).map(String::as_str), Some("161"));
+        assert_eq!(answers.get(&2).map(String::as_str), Some("48"));
+        assert!(parse_answers("1").is_err());
+        assert!(parse_answers("one 161").is_err());
+    }
+
+    #[test]
+    fn every_day_has_an_example() {
+        let fixtures = discover(Path::new(FIXTURE_DIR)).unwrap();
+        for entry in DAYS {
+            assert!(
+                fixtures.iter().any(|fixture| fixture.day == entry.day),
+                "no examples in {}",
+                day_dir(entry.day)
+            );
+        }
+    }
+
+    #[test]
+    fn examples() {
+        let fixtures = discover(Path::new(FIXTURE_DIR)).unwrap();
+        if let Err(failures) = check_all(&fixtures) {
+            panic!("failing examples:\n{failures}");
+        }
+    }
+
+    #[test]
+    fn failing_example() {
+        let fixture = Fixture {
+            day: 1,
+            path: PathBuf::from(day_dir(1)).join("example1.txt"),
+            answers: BTreeMap::from([(1, "12".to_owned()), (2, "31".to_owned())]),
+        };
+        assert_eq!(
+            fixture.check(),
+            Ok(vec![
+                (
+                    1,
+                    Outcome::Fail {
+                        expected: "12".into(),
+                        actual: "11".into()
+                    }
+                ),
+                (2, Outcome::Pass("31".into())),
+            ])
+        );
+        assert!(
+            check_all(&[fixture])
+                .unwrap_err()
+                .ends_with("part 1: FAIL expected 12, got 11")
+        );
+    }
+}
@@ -0,0 +1,531 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+pub const DEFAULT_SEED: u64 = 2024;
+
+// splitmix64, small and good enough to make inputs that look random
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let span = range.end() - range.start() + 1;
+        range.start() + self.next_u64() % span
+    }
+
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.range(0..=bound as u64 - 1) as usize
+    }
+
+    // true once every `n` calls on average
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.next_u64().is_multiple_of(n)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Shape {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Shape {
+    // "WxH", or a single number for a square
+    pub fn parse(value: &str) -> Result<Shape, String> {
+        let invalid = || format!("invalid size {value:?}, expected <width>x<height> or <n>");
+        let (width, height) = value.split_once('x').unwrap_or((value, value));
+        let shape = Shape {
+            width: width.parse().map_err(|_| invalid())?,
+            height: height.parse().map_err(|_| invalid())?,
+        };
+        if shape.width == 0 || shape.height == 0 {
+            return Err(invalid());
+        }
+        Ok(shape)
+    }
+}
+
+#[derive(Debug)]
+pub struct Generator {
+    pub day: u8,
+    pub shape: Shape,
+    pub generate: fn(&mut Rng, Shape) -> String,
+}
+
+impl Generator {
+    const fn new(
+        day: u8,
+        (width, height): (usize, usize),
+        generate: fn(&mut Rng, Shape) -> String,
+    ) -> Self {
+        Generator {
+            day,
+            shape: Shape { width, height },
+            generate,
+        }
+    }
+}
+
+// the default shapes are about the size of the real inputs
+pub const GENERATORS: &[Generator] = &[
+    Generator::new(1, (1, 1000), day1), // height pairs of location ids
+    Generator::new(2, (8, 1000), day2), // height reports of up to width levels
+    Generator::new(3, (3000, 6), day3), // height lines of width characters
+    Generator::new(4, (140, 140), letter_grid), // width x height letters
+    Generator::new(5, (49, 200), day5), // width pages and height updates
+    Generator::new(6, (130, 130), day6), // width x height lab map
+    Generator::new(7, (9, 850), day7),  // height equations of up to width numbers
+    Generator::new(8, (50, 50), day8),  // width x height antenna map
+    Generator::new(9, (20000, 1), day9), // width digits
+    Generator::new(10, (50, 50), day10), // width x height topographic map
+    Generator::new(11, (8, 1), day11),  // width stones
+    Generator::new(12, (140, 140), day12), // width x height garden
+    Generator::new(13, (1, 320), day13), // height claw machines
+];
+
+pub fn find_generator(day: u8) -> Result<&'static Generator, String> {
+    GENERATORS
+        .iter()
+        .find(|generator| generator.day == day)
+        .ok_or(format!("no generator for day {day}"))
+}
+
+pub fn generate(day: u8, shape: Option<Shape>, seed: u64) -> Result<String, String> {
+    let generator = find_generator(day)?;
+    let mut rng = Rng::new(seed);
+    Ok((generator.generate)(
+        &mut rng,
+        shape.unwrap_or(generator.shape),
+    ))
+}
+
+fn grid(shape: Shape, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    let mut grid = String::with_capacity((shape.width + 1) * shape.height);
+    for y in 0..shape.height {
+        grid.extend((0..shape.width).map(|x| cell(x, y)));
+        grid.push('\n');
+    }
+    grid
+}
+
+fn day1(rng: &mut Rng, shape: Shape) -> String {
+    let mut input = String::new();
+    for _ in 0..shape.height {
+        let (left, right) = (rng.range(10000..=99999), rng.range(10000..=99999));
+        writeln!(input, "{left}   {right}").unwrap();
+    }
+    input
+}
+
+// mostly safe reports, with an occasional step that is too big, flat or turning
+fn day2(rng: &mut Rng, shape: Shape) -> String {
+    let mut input = String::new();
+    for _ in 0..shape.height {
+        let length = rng.range(shape.width.div_ceil(2) as u64..=shape.width as u64);
+        let direction = if rng.one_in(2) { 1 } else { -1 };
+        let mut level = rng.range(20..=80) as i64;
+        let mut levels = Vec::new();
+        for _ in 0..length {
+            levels.push(level.to_string());
+            let step = if rng.one_in(10) {
+                rng.pick(&[0, 4, 5, -1, -2])
+            } else {
+                rng.range(1..=3) as i64
+            };
+            level += direction * step;
+        }
+        input += &levels.join(" ");
+        input.push('\n');
+    }
+    input
+}
+
+fn day3(rng: &mut Rng, shape: Shape) -> String {
+    const NOISE: &[&str] = &[
+        "!",
+        "@",
+        "#",
+        "$",
+        "%",
+        "^",
+        "&",
+        "*",
+        "(",
+        ")",
+        "[",
+        "]",
+        "<",
+        ">",
+        ",",
+        "+",
+        "-",
+        "?",
+        " ",
+        "'",
+        "mul",
+        "mul(",
+        "do",
+        "don't",
+        "from()",
+        "what()",
+        "mul[3,7]",
+        "mul(4*",
+        "mul ( 2 , 4 )",
+    ];
+    let mut input = String::new();
+    for _ in 0..shape.height {
+        let mut line = String::new();
+        while line.len() < shape.width {
+            match rng.below(10) {
+                0..=5 => line += rng.pick(NOISE),
+                6 | 7 => {
+                    let (a, b) = (rng.range(1..=999), rng.range(1..=999));
+                    write!(line, "mul({a},{b})").unwrap();
+                }
+                8 => line += "do()",
+                _ => line += "don't()",
+            }
+        }
+        input += &line;
+        input.push('\n');
+    }
+    input
+}
+
+fn letter_grid(rng: &mut Rng, shape: Shape) -> String {
+    grid(shape, |_, _| rng.pick(&['X', 'M', 'A', 'S']))
+}
+
+// rules order every pair of pages, so each update has exactly one correct order
+fn day5(rng: &mut Rng, shape: Shape) -> String {
+    let mut pages: Vec<u64> = (10..=99).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(shape.width.clamp(3, pages.len()));
+
+    let mut rules = Vec::new();
+    for (index, before) in pages.iter().enumerate() {
+        for after in &pages[index + 1..] {
+            rules.push(format!("{before}|{after}"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let max_length = ((pages.len().min(23) - 1) | 1) as u64;
+    let mut updates = Vec::new();
+    for _ in 0..shape.height {
+        let length = rng.range(3..=max_length) as usize | 1;
+        let mut indices: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut indices);
+        indices.truncate(length);
+        if rng.one_in(2) {
+            indices.sort();
+        }
+        let update: Vec<String> = indices
+            .iter()
+            .map(|&index| pages[index].to_string())
+            .collect();
+        updates.push(update.join(","));
+    }
+    format!("{}\n\n{}\n", rules.join("\n"), updates.join("\n"))
+}
+
+// the cells around the guard stay clear, and maps where it never gets out have no part 1
+// answer so those are rolled again
+fn day6(rng: &mut Rng, shape: Shape) -> String {
+    loop {
+        let guard = (rng.below(shape.width), rng.below(shape.height));
+        let map = grid(shape, |x, y| match (x, y) {
+            position if position == guard => '^',
+            _ if x.abs_diff(guard.0) <= 1 && y.abs_diff(guard.1) <= 1 => '.',
+            _ if rng.one_in(12) => '#',
+            _ => '.',
+        });
+        if guard_exits(&map, guard) {
+            return map;
+        }
+    }
+}
+
+// walks the guard up from `(x, y)` until it leaves the map or repeats a turn
+fn guard_exits(map: &str, (mut x, mut y): (usize, usize)) -> bool {
+    let rows: Vec<&[u8]> = map.lines().map(str::as_bytes).collect();
+    let (mut dx, mut dy) = (0, -1);
+    let mut turns = HashSet::new();
+    loop {
+        let next = x
+            .checked_add_signed(dx)
+            .zip(y.checked_add_signed(dy))
+            .filter(|&(x, y)| y < rows.len() && x < rows[y].len());
+        let Some((next_x, next_y)) = next else {
+            return true;
+        };
+        if rows[next_y][next_x] == b'#' {
+            (dx, dy) = (-dy, dx);
+            if !turns.insert((x, y, dx, dy)) {
+                return false;
+            }
+        } else {
+            (x, y) = (next_x, next_y);
+        }
+    }
+}
+
+// the concatenation of all numbers stays within an i64, so no combination of operators overflows
+fn day7(rng: &mut Rng, shape: Shape) -> String {
+    const MAX_DIGITS: usize = 18;
+    let mut input = String::new();
+    for _ in 0..shape.height {
+        let count = rng.range(2..=shape.width.clamp(2, MAX_DIGITS) as u64);
+        let mut numbers = Vec::new();
+        let mut digits = 0;
+        while numbers.len() < count as usize {
+            let number = rng.range(1..=if digits + 2 <= MAX_DIGITS { 99 } else { 9 });
+            if digits + number.to_string().len() > MAX_DIGITS {
+                break;
+            }
+            digits += number.to_string().len();
+            numbers.push(number as i64);
+        }
+        let mut result = numbers[0];
+        for &number in &numbers[1..] {
+            result = match rng.below(3) {
+                0 => result + number,
+                1 => result * number,
+                _ => format!("{result}{number}").parse().unwrap(),
+            };
+        }
+        if rng.one_in(2) {
+            result += 1;
+        }
+        let numbers: Vec<String> = numbers.iter().map(i64::to_string).collect();
+        writeln!(input, "{result}: {}", numbers.join(" ")).unwrap();
+    }
+    input
+}
+
+fn day8(rng: &mut Rng, shape: Shape) -> String {
+    const FREQUENCIES: &[char] = &['a', 'b', 'A', 'B', 'x', 'Z', '0', '1', '7', '9'];
+    grid(shape, |_, _| {
+        if rng.one_in(25) {
+            rng.pick(FREQUENCIES)
+        } else {
+            '.'
+        }
+    })
+}
+
+// files are never empty, free space can be
+fn day9(rng: &mut Rng, shape: Shape) -> String {
+    let mut input: String = (0..shape.width)
+        .map(|index| {
+            let digit = if index % 2 == 0 {
+                rng.range(1..=9)
+            } else {
+                rng.range(0..=9)
+            };
+            char::from(b'0' + digit as u8)
+        })
+        .collect();
+    input.push('\n');
+    input
+}
+
+// heights rise and fall diagonally with some noise, so there are trails to follow
+fn day10(rng: &mut Rng, shape: Shape) -> String {
+    grid(shape, |x, y| {
+        let wave = (x + y) % 18;
+        let height = if rng.one_in(6) {
+            rng.below(10)
+        } else {
+            wave.min(18 - wave)
+        };
+        char::from(b'0' + height as u8)
+    })
+}
+
+fn day11(rng: &mut Rng, shape: Shape) -> String {
+    let stones: Vec<String> = (0..shape.width)
+        .map(|_| rng.range(0..=999_999).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
+// crops mostly copy a neighbour, so regions grow into blobs
+fn day12(rng: &mut Rng, shape: Shape) -> String {
+    let mut crops = vec![vec!['A'; shape.width]; shape.height];
+    for y in 0..shape.height {
+        for x in 0..shape.width {
+            crops[y][x] = match (x, y) {
+                (_, 1..) if rng.one_in(3) => crops[y - 1][x],
+                (1.., _) if !rng.one_in(4) => crops[y][x - 1],
+                _ => char::from(b'A' + rng.below(26) as u8),
+            };
+        }
+    }
+    grid(shape, |x, y| crops[y][x])
+}
+
+// a third of the prizes can't be reached
+fn day13(rng: &mut Rng, shape: Shape) -> String {
+    let mut machines = Vec::new();
+    while machines.len() < shape.height {
+        let a = (rng.range(10..=99), rng.range(10..=99));
+        let b = (rng.range(10..=99), rng.range(10..=99));
+        if a.0 * b.1 == a.1 * b.0 {
+            continue;
+        }
+        let (presses_a, presses_b) = (rng.range(0..=100), rng.range(0..=100));
+        let mut prize = (
+            presses_a * a.0 + presses_b * b.0,
+            presses_a * a.1 + presses_b * b.1,
+        );
+        if rng.one_in(3) {
+            prize.0 += rng.range(1..=50);
+        }
+        machines.push(format!(
+            "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+            a.0, a.1, b.0, b.1, prize.0, prize.1
+        ));
+    }
+    machines.join("\n")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::day9;
+    use crate::runner::DAYS;
+
+    #[test]
+    fn same_seed_same_input() {
+        for generator in GENERATORS {
+            let shape = Some(Shape {
+                width: 10,
+                height: 10,
+            });
+            let input = generate(generator.day, shape, 7).unwrap();
+            assert_eq!(generate(generator.day, shape, 7).unwrap(), input);
+            assert_ne!(generate(generator.day, shape, 8).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn every_day_has_a_generator() {
+        for entry in DAYS {
+            assert!(find_generator(entry.day).is_ok(), "day {}", entry.day);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        let shape = Some(Shape {
+            width: 12,
+            height: 12,
+        });
+        for entry in DAYS {
+            for seed in 0..3 {
+                let input = generate(entry.day, shape, seed).unwrap();
+                for part in [1, 2] {
+                    let answer = (entry.solve)(&input, part);
+                    assert!(answer.is_ok(), "day {} part {part}: {answer:?}", entry.day);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day6_guard_starts_free() {
+        let day = DAYS.iter().find(|entry| entry.day == 6).unwrap();
+        for seed in 90..110 {
+            let shape = Shape {
+                width: 8,
+                height: 8,
+            };
+            let input = generate(6, Some(shape), seed).unwrap();
+            let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+            let y = rows.iter().position(|row| row.contains(&b'^')).unwrap();
+            let x = rows[y].iter().position(|&c| c == b'^').unwrap();
+            for row in &rows[y.saturating_sub(1)..(y + 2).min(rows.len())] {
+                assert!(!row[x.saturating_sub(1)..(x + 2).min(row.len())].contains(&b'#'));
+            }
+            for part in [1, 2] {
+                let answer = (day.solve)(&input, part);
+                assert!(answer.is_ok(), "seed {seed} part {part}: {answer:?}");
+            }
+        }
+        // part 2 tries every cell, which takes too long on maps this size
+        for seed in 1..=5 {
+            let shape = Shape {
+                width: 200,
+                height: 200,
+            };
+            let input = generate(6, Some(shape), seed).unwrap();
+            let answer = (day.solve)(&input, 1);
+            assert!(answer.is_ok(), "seed {seed}: {answer:?}");
+        }
+    }
+
+    #[test]
+    fn guard_exits() {
+        assert!(super::guard_exits("...\n.^.\n...\n", (1, 1)));
+        assert!(super::guard_exits(".#.\n...\n.^#\n", (1, 2)));
+        assert!(!super::guard_exits(".#..\n...#\n#^..\n..#.\n", (1, 2)));
+        assert!(!super::guard_exits(".#.\n#^#\n.#.\n", (1, 1)));
+    }
+
+    #[test]
+    fn shapes() {
+        assert_eq!(
+            Shape::parse("100000x1"),
+            Ok(Shape {
+                width: 100000,
+                height: 1
+            })
+        );
+        assert_eq!(
+            Shape::parse("12"),
+            Ok(Shape {
+                width: 12,
+                height: 12
+            })
+        );
+        assert!(Shape::parse("0x3").is_err());
+        assert!(Shape::parse("3x").is_err());
+
+        let shape = Shape {
+            width: 31,
+            height: 7,
+        };
+        let input = generate(6, Some(shape), 1).unwrap();
+        assert_eq!(input.lines().count(), 7);
+        assert!(input.lines().all(|line| line.len() == 31));
+        assert_eq!(input.matches('^').count(), 1);
+        let input = generate(9, Some(shape), 1).unwrap();
+        assert_eq!(day9::parse_digits(input.trim()).unwrap().len(), 31);
+    }
+}
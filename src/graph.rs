@@ -0,0 +1,384 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+
+// a directed graph, nodes and edges are kept in insertion order so every traversal is
+// deterministic
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    edges: Vec<Vec<usize>>,
+}
+
+// nodes along a cycle, each with an edge to the next and the last with an edge to the first
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Display> Display for Cycle<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "cycle ")?;
+        for node in &self.0 {
+            write!(f, "{node} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first}"),
+            None => write!(f, "(empty)"),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Graph<N> {
+        Graph {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    // adding a node twice keeps the first one
+    pub fn add_node(&mut self, node: N) {
+        self.index_or_insert(node);
+    }
+
+    // adds missing nodes, parallel edges are kept once
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.index_or_insert(from);
+        let to = self.index_or_insert(to);
+        if !self.edges[from].contains(&to) {
+            self.edges[from].push(to);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let edges = match self.indices.get(node) {
+            Some(&index) => self.edges[index].as_slice(),
+            None => &[],
+        };
+        edges.iter().map(|&index| &self.nodes[index])
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        match (self.indices.get(from), self.indices.get(to)) {
+            (Some(&from), Some(&to)) => self.edges[from].contains(&to),
+            _ => false,
+        }
+    }
+
+    // depth first, the order is the reverse of the finishing order
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnStack,
+            Done,
+        }
+        let mut states = vec![State::New; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+        for root in 0..self.len() {
+            if states[root] != State::New {
+                continue;
+            }
+            // the current path with the next edge to follow from each node
+            let mut stack = vec![(root, 0)];
+            states[root] = State::OnStack;
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                let Some(&successor) = self.edges[node].get(*next) else {
+                    states[node] = State::Done;
+                    finished.push(node);
+                    stack.pop();
+                    continue;
+                };
+                *next += 1;
+                match states[successor] {
+                    State::New => {
+                        states[successor] = State::OnStack;
+                        stack.push((successor, 0));
+                    }
+                    State::OnStack => {
+                        let start = stack.iter().position(|&(n, _)| n == successor).unwrap();
+                        return Err(self.cycle(stack[start..].iter().map(|&(n, _)| n)));
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        finished.reverse();
+        Ok(self.resolve(finished))
+    }
+
+    // Kahn's algorithm, ties are broken by insertion order
+    pub fn topological_sort_kahn(&self) -> Result<Vec<N>, Cycle<N>> {
+        let mut in_degrees = vec![0; self.len()];
+        for &to in self.edges.iter().flatten() {
+            in_degrees[to] += 1;
+        }
+        let mut ready: VecDeque<usize> = (0..self.len()).filter(|&n| in_degrees[n] == 0).collect();
+        let mut sorted = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            sorted.push(node);
+            for &successor in &self.edges[node] {
+                in_degrees[successor] -= 1;
+                if in_degrees[successor] == 0 {
+                    ready.push_back(successor);
+                }
+            }
+        }
+        if sorted.len() == self.len() {
+            return Ok(self.resolve(sorted));
+        }
+
+        // every node left has a predecessor that's also left, walking back must close a loop
+        let predecessors = self.reversed_edges();
+        let mut node = (0..self.len()).find(|&n| in_degrees[n] > 0).unwrap();
+        let mut path = Vec::new();
+        let mut seen = HashMap::new();
+        while !seen.contains_key(&node) {
+            seen.insert(node, path.len());
+            path.push(node);
+            node = *predecessors[node]
+                .iter()
+                .find(|&&p| in_degrees[p] > 0)
+                .unwrap();
+        }
+        let mut cycle = path.split_off(seen[&node]);
+        cycle.reverse();
+        Err(self.cycle(cycle))
+    }
+
+    // Kosaraju's algorithm, components come in topological order and each is ordered by
+    // insertion
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut visited = vec![false; self.len()];
+        let mut finished = Vec::with_capacity(self.len());
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut stack = vec![(root, 0)];
+            while let Some((node, next)) = stack.last_mut() {
+                let node = *node;
+                match self.edges[node].get(*next) {
+                    Some(&successor) => {
+                        *next += 1;
+                        if !visited[successor] {
+                            visited[successor] = true;
+                            stack.push((successor, 0));
+                        }
+                    }
+                    None => {
+                        finished.push(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        let predecessors = self.reversed_edges();
+        let mut component_of = vec![None; self.len()];
+        let mut components = Vec::new();
+        for &root in finished.iter().rev() {
+            if component_of[root].is_some() {
+                continue;
+            }
+            let id = components.len();
+            let mut component = vec![root];
+            component_of[root] = Some(id);
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &predecessor in &predecessors[node] {
+                    if component_of[predecessor].is_none() {
+                        component_of[predecessor] = Some(id);
+                        component.push(predecessor);
+                        stack.push(predecessor);
+                    }
+                }
+            }
+            component.sort_unstable();
+            components.push(self.resolve(component));
+        }
+        components
+    }
+
+    // nodes at the end of a path of at least one edge from `from`, in breadth first order
+    pub fn reachable_from(&self, from: &N) -> Vec<N> {
+        match self.indices.get(from) {
+            Some(&from) => self.resolve(self.reach(from)),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn is_reachable(&self, from: &N, to: &N) -> bool {
+        match (self.indices.get(from), self.indices.get(to)) {
+            (Some(&from), Some(to)) => self.reach(from).contains(to),
+            _ => false,
+        }
+    }
+
+    // an edge from every node to every node it can reach
+    pub fn transitive_closure(&self) -> Graph<N> {
+        Graph {
+            nodes: self.nodes.clone(),
+            indices: self.indices.clone(),
+            edges: (0..self.len()).map(|node| self.reach(node)).collect(),
+        }
+    }
+
+    fn index_or_insert(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.indices.insert(node, index);
+        self.edges.push(Vec::new());
+        index
+    }
+
+    fn reach(&self, from: usize) -> Vec<usize> {
+        let mut seen = HashSet::new();
+        let mut reached = Vec::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            for &successor in &self.edges[node] {
+                if seen.insert(successor) {
+                    reached.push(successor);
+                    queue.push_back(successor);
+                }
+            }
+        }
+        reached
+    }
+
+    fn reversed_edges(&self) -> Vec<Vec<usize>> {
+        let mut reversed = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &to in edges {
+                reversed[to].push(from);
+            }
+        }
+        reversed
+    }
+
+    fn resolve(&self, indices: Vec<usize>) -> Vec<N> {
+        indices
+            .into_iter()
+            .map(|index| self.nodes[index].clone())
+            .collect()
+    }
+
+    fn cycle(&self, indices: impl IntoIterator<Item = usize>) -> Cycle<N> {
+        Cycle(self.resolve(indices.into_iter().collect()))
+    }
+}
+
+impl<N: Clone + Eq + Hash> Default for Graph<N> {
+    fn default() -> Graph<N> {
+        Graph::new()
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for Graph<N> {
+    fn from_iter<I: IntoIterator<Item = (N, N)>>(edges: I) -> Graph<N> {
+        let mut graph = Graph::new();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // checks that every node of a cycle has an edge to the next one
+    fn assert_cycle(graph: &Graph<i32>, Cycle(nodes): &Cycle<i32>) {
+        assert!(!nodes.is_empty());
+        for (i, from) in nodes.iter().enumerate() {
+            let to = &nodes[(i + 1) % nodes.len()];
+            assert!(graph.has_edge(from, to), "no edge {from} -> {to}");
+        }
+    }
+
+    #[test]
+    fn sort() {
+        let graph: Graph<i32> = [(1, 2), (1, 3), (2, 4), (4, 5), (1, 5), (2, 3), (3, 4)]
+            .into_iter()
+            .collect();
+        assert_eq!(graph.topological_sort(), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(graph.topological_sort_kahn(), Ok(vec![1, 2, 3, 4, 5]));
+
+        let mut graph: Graph<i32> = [(3, 1), (2, 1)].into_iter().collect();
+        graph.add_node(0);
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 2, 3, 1]));
+        assert_eq!(graph.topological_sort_kahn(), Ok(vec![3, 2, 0, 1]));
+    }
+
+    #[test]
+    fn report_cycles() {
+        let graph: Graph<i32> = [(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]
+            .into_iter()
+            .collect();
+        let cycle = graph.topological_sort().unwrap_err();
+        assert_eq!(cycle, Cycle(vec![1, 2, 3]));
+        assert_eq!(cycle.to_string(), "cycle 1 -> 2 -> 3 -> 1");
+        let cycle = graph.topological_sort_kahn().unwrap_err();
+        assert_cycle(&graph, &cycle);
+        assert_eq!(cycle.0.len(), 3);
+
+        let graph: Graph<i32> = [(7, 7)].into_iter().collect();
+        assert_eq!(graph.topological_sort(), Err(Cycle(vec![7])));
+        assert_eq!(graph.topological_sort_kahn(), Err(Cycle(vec![7])));
+    }
+
+    #[test]
+    fn components() {
+        let graph: Graph<char> = "ab bc ca cd de ed ef"
+            .split(' ')
+            .map(|edge| (edge.as_bytes()[0] as char, edge.as_bytes()[1] as char))
+            .collect();
+        assert_eq!(
+            graph.strongly_connected_components(),
+            [vec!['a', 'b', 'c'], vec!['d', 'e'], vec!['f']]
+        );
+    }
+
+    #[test]
+    fn reachability() {
+        let mut graph: Graph<i32> = [(1, 2), (2, 3), (3, 2), (4, 1)].into_iter().collect();
+        graph.add_node(5);
+        assert_eq!(graph.reachable_from(&1), [2, 3]);
+        assert_eq!(graph.reachable_from(&2), [3, 2]);
+        assert!(graph.reachable_from(&5).is_empty());
+        assert!(graph.is_reachable(&4, &3));
+        assert!(!graph.is_reachable(&3, &1));
+        assert!(!graph.is_reachable(&1, &6));
+
+        let closure = graph.transitive_closure();
+        assert!(closure.has_edge(&4, &3));
+        assert!(closure.has_edge(&2, &2));
+        assert!(!closure.has_edge(&1, &1));
+        assert_eq!(closure.successors(&4).count(), 3);
+        assert_eq!(closure.len(), 5);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::error::ParseError;
+use crate::position::{Direction, Position};
+use std::ops::{Index, IndexMut};
+
+// (x, y) where x is the column from the left and y the row from the first line of the input,
+// so y grows downwards, the same orientation as Position
+pub type Point = (usize, usize);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "cells don't fill {width}x{height}"
+        );
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    // one row per line, surrounding whitespace of the input and of every line is ignored
+    pub fn parse(
+        day: u8,
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, &'static str>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in input.trim().lines().map(str::trim) {
+            let start = cells.len();
+            for (i, c) in line.char_indices() {
+                let fragment = &line[i..i + c.len_utf8()];
+                cells.push(
+                    cell(c).map_err(|message| ParseError::new(day, input, fragment, message))?,
+                );
+            }
+            let columns = cells.len() - start;
+            match width {
+                None => width = Some(columns),
+                Some(width) if width != columns => {
+                    let message = format!("expected {width} columns, found {columns}");
+                    return Err(ParseError::new(day, input, line, message));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point).then(|| &self.cells[self.index(point)])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if !self.contains(point) {
+            return None;
+        }
+        let index = self.index(point);
+        Some(&mut self.cells[index])
+    }
+
+    // the next point in `direction`, if it's still on the grid
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        Position::from(point)
+            .step_within(direction, self.size())
+            .map(Point::from)
+    }
+
+    // row by row, left to right
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| (index % width, index / width))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    // `start` and every point after it in `direction` until the edge
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        let start = self.contains(start).then_some(start);
+        std::iter::successors(start, move |&point| self.step(point, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.ray((x, 0), Direction::Down).map(|point| &self[point])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // going down and right, starting from the left column and then the top row
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height).rev().map(|y| (0, y));
+        let starts = starts.chain((1..self.width).map(|x| (x, 0)));
+        starts.map(|start| {
+            self.ray(start, Direction::DownRight)
+                .map(|point| &self[point])
+        })
+    }
+
+    // going down and left, starting from the top row and then the right column
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.width).map(|x| (x, 0));
+        let right = self.width.saturating_sub(1);
+        let starts = starts.chain((1..self.height).map(move |y| (right, y)));
+        starts.map(|start| {
+            self.ray(start, Direction::DownLeft)
+                .map(|point| &self[point])
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn index(&self, (x, y): Point) -> usize {
+        y * self.width + x
+    }
+}
+
+impl Grid<char> {
+    pub fn chars(day: u8, input: &str) -> Result<Grid<char>, ParseError> {
+        Grid::parse(day, input, Ok)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside {}x{}", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{point:?} is outside {width}x{height}"))
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        &self[Point::from(position)]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        &mut self[Point::from(position)]
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::chars(0, "abc\ndef").unwrap()
+    }
+
+    #[test]
+    fn parse_grid() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(
+            Grid::chars(0, "\n    ab\n    cd\n").unwrap().row(1),
+            ['c', 'd']
+        );
+        assert_eq!(Grid::chars(0, "").unwrap().points().count(), 0);
+
+        let error = Grid::chars(4, "abc\nde").unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (4, 2, 1));
+        assert_eq!(error.message, "expected 3 columns, found 2");
+        let digit = |c: char| c.to_digit(10).ok_or("invalid digit");
+        let error = Grid::parse(10, "12\n3x", digit).unwrap_err();
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 2, "x"));
+        assert_eq!(error.message, "invalid digit");
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = sample();
+        let corner: Vec<Point> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(corner, [(1, 0), (0, 1)]);
+        let middle: Vec<Point> = grid.neighbours4((1, 1)).collect();
+        assert_eq!(middle, [(1, 0), (2, 1), (0, 1)]);
+        let all: Vec<Point> = grid.neighbours8((1, 1)).collect();
+        assert_eq!(all, [(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]);
+        assert_eq!(grid.step((2, 1), Direction::Right), None);
+        assert_eq!(grid.step((2, 1), Direction::UpLeft), Some((1, 0)));
+        assert_eq!(grid[Position::new(1, 1)], 'e');
+    }
+
+    #[test]
+    fn lines() {
+        let grid = Grid::chars(0, "abc\ndef\nghi").unwrap();
+        let text = |line: &mut dyn Iterator<Item = &char>| line.collect::<String>();
+        let columns: Vec<String> = grid.columns().map(|mut column| text(&mut column)).collect();
+        assert_eq!(columns, ["adg", "beh", "cfi"]);
+        let diagonals: Vec<String> = grid.diagonals().map(|mut line| text(&mut line)).collect();
+        assert_eq!(diagonals, ["g", "dh", "aei", "bf", "c"]);
+        let anti: Vec<String> = grid
+            .anti_diagonals()
+            .map(|mut line| text(&mut line))
+            .collect();
+        assert_eq!(anti, ["a", "bd", "ceg", "fh", "i"]);
+        let ray: Vec<Point> = grid.ray((0, 2), Direction::UpRight).collect();
+        assert_eq!(ray, [(0, 2), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn update_cells() {
+        let mut grid = sample();
+        grid[(1, 0)] = 'x';
+        *grid.get_mut((2, 1)).unwrap() = 'y';
+        assert_eq!(grid.get_mut((3, 1)), None);
+        assert_eq!(grid.get_mut((0, usize::MAX)), None);
+        assert_eq!(grid.get((0, usize::MAX)), None);
+        assert_eq!(grid.position(|&c| c == 'y'), Some((2, 1)));
+        let upper = grid.map(|c| c.to_ascii_uppercase());
+        assert_eq!(
+            upper.rows().collect::<Vec<_>>(),
+            [['A', 'X', 'C'], ['D', 'E', 'Y']]
+        );
+        assert_eq!(Grid::filled(2, 1, 0).iter().count(), 2);
+    }
+}
@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_DIR: &str = "artifacts/input_files";
+pub const DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+pub const STDIN: &str = "-";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+// where to look for a day's input, an explicit path wins over the directory
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct InputOptions {
+    pub dir: Option<String>,
+    pub path: Option<String>,
+}
+
+impl InputOptions {
+    pub fn resolve(&self, day: u8) -> Source {
+        match self.path.as_deref() {
+            Some(STDIN) => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None => {
+                let dir = self
+                    .dir
+                    .clone()
+                    .or_else(|| env::var(DIR_VARIABLE).ok())
+                    .unwrap_or_else(|| DEFAULT_DIR.to_owned());
+                Source::File(Path::new(&dir).join(file_name(day)))
+            }
+        }
+    }
+
+    pub fn read(&self, day: u8) -> Result<String, String> {
+        self.resolve(day).read()
+    }
+}
+
+impl Source {
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|error| format!("can't read stdin: {error}"))?;
+                Ok(input)
+            }
+            Source::File(path) => fs::read_to_string(path)
+                .map_err(|error| format!("can't read {}: {error}", path.display())),
+        }
+    }
+}
+
+pub fn file_name(day: u8) -> String {
+    format!("input_day{day}.txt")
+}
+
+// parsers only ever see LF line endings and no trailing newlines
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.trim_end_matches(['\r', '\n']);
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn explicit_path() {
+        let options = InputOptions {
+            dir: Some("elsewhere".into()),
+            path: Some("my_input.txt".into()),
+        };
+        assert_eq!(options.resolve(3), Source::File("my_input.txt".into()));
+    }
+
+    #[test]
+    fn stdin_path() {
+        let options = InputOptions {
+            dir: None,
+            path: Some("-".into()),
+        };
+        assert_eq!(options.resolve(3), Source::Stdin);
+    }
+
+    #[test]
+    fn directory_override() {
+        let options = InputOptions {
+            dir: Some("inputs".into()),
+            path: None,
+        };
+        assert_eq!(
+            options.resolve(12),
+            Source::File(Path::new("inputs").join("input_day12.txt"))
+        );
+    }
+
+    #[test]
+    fn default_directory_has_every_day() {
+        if env::var(DIR_VARIABLE).is_ok() {
+            return;
+        }
+        for day in [1, 2, 3, 13] {
+            let input = InputOptions::default().read(day);
+            assert!(input.is_ok(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn normalize_line_endings() {
+        let expected = "a b\n\nc";
+        for input in [
+            "a b\n\nc",
+            "a b\r\n\r\nc",
+            "a b\n\nc\n",
+            "a b\r\n\r\nc\r\n\r\n",
+        ] {
+            assert_eq!(normalize(input), expected);
+        }
+        assert!(matches!(normalize("a\nb\n"), Cow::Borrowed("a\nb")));
+    }
+
+    #[test]
+    fn missing_file() {
+        let options = InputOptions {
+            dir: None,
+            path: Some("no/such/file.txt".into()),
+        };
+        assert!(
+            options
+                .read(1)
+                .unwrap_err()
+                .starts_with("can't read no/such/file.txt")
+        );
+    }
+}
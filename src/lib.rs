@@ -0,0 +1,69 @@
+pub mod answer;
+pub mod bench;
+pub mod check;
+pub mod day1;
+pub mod day10;
+pub mod day10_part2;
+pub mod day11;
+pub mod day11_part2;
+pub mod day12;
+pub mod day13;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day4_part2;
+pub mod day5;
+pub mod day5_part2;
+pub mod day6;
+pub mod day6_part2;
+pub mod day7;
+pub mod day7_part2;
+pub mod day8;
+pub mod day8_part2;
+pub mod day9;
+pub mod day9_part2;
+pub mod error;
+pub mod fetch;
+pub mod fixture;
+pub mod generate;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod memo;
+pub mod pathfinding;
+pub mod pool;
+pub mod position;
+pub mod ppm;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod scan;
+pub mod server;
+pub mod solution;
+pub mod template;
+pub mod vector;
+
+pub use error::ParseError;
+pub use input::{InputOptions, normalize};
+pub use solution::{Solution, solve};
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn public_api() {
+        let regions = day12::Day12::parse("AAAA\nBBCD\nBBCC\nEEEC").unwrap();
+        let fence_cost = day12::calculate_fence_cost(&regions);
+        assert_eq!(fence_cost, Ok(140));
+        assert_eq!(regions[0].fence_cost(), Ok(40));
+        let mut line = day11_part2::StonesLine::from_numbers(&[125, 17]);
+        line.blink_n_times(25).unwrap();
+        assert_eq!(line.count_stones(), Ok(55312));
+        assert_eq!(
+            solve::<day1::Day1>("3   4\n4   3\n2   5", 1),
+            Ok("3".to_string())
+        );
+    }
+}
@@ -1,13 +1,24 @@
+use day1q1::runner;
+use std::env;
+use std::process::ExitCode;
 
-mod day13;
-
-fn main() {
-    day13::main();
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = match runner::Args::parse(&args) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("error: {error}\n{}", runner::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match runner::run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
 }
 
-
 #[cfg(test)]
-mod tests {
-    
-   
-}
+mod tests {}
@@ -0,0 +1,200 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+// a cache for a pure function, values are only computed on a miss
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    values: HashMap<K, V>,
+    // keys from oldest to newest, only kept when the size is bounded
+    order: VecDeque<K>,
+    capacity: Option<usize>,
+    stats: Stats,
+}
+
+impl<K: Clone + Eq + Hash, V> Memo<K, V> {
+    pub fn new() -> Memo<K, V> {
+        Memo {
+            values: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: None,
+            stats: Stats::default(),
+        }
+    }
+
+    // keeps at most `capacity` values, the oldest are evicted first
+    pub fn bounded(capacity: usize) -> Memo<K, V> {
+        assert!(capacity > 0, "a memo needs room for at least one value");
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::new()
+        }
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&K) -> V) -> &V {
+        if self.values.contains_key(&key) {
+            self.stats.hits += 1;
+            return &self.values[&key];
+        }
+        self.stats.misses += 1;
+        let value = compute(&key);
+        self.insert(key.clone(), value);
+        &self.values[&key]
+    }
+
+    // for recursive definitions, `f` receives the memo so it can look up smaller keys with
+    // the same function
+    pub fn recurse(&mut self, key: K, f: fn(&mut Memo<K, V>, &K) -> V) -> V
+    where
+        V: Clone,
+    {
+        if let Some(value) = self.values.get(&key) {
+            self.stats.hits += 1;
+            return value.clone();
+        }
+        self.stats.misses += 1;
+        let value = f(self, &key);
+        self.insert(key, value.clone());
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // forgets every value but keeps the statistics
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.order.clear();
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        let Some(capacity) = self.capacity else {
+            self.values.insert(key, value);
+            return;
+        };
+        // a recursive call may have stored the same key already
+        if self.values.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.values.len() > capacity {
+            let oldest = self.order.pop_front().unwrap();
+            self.values.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+// a function bundled with the memo of its results
+#[derive(Debug, Clone)]
+pub struct Memoized<K, V, F> {
+    function: F,
+    memo: Memo<K, V>,
+}
+
+impl<K: Clone + Eq + Hash, V, F: Fn(&K) -> V> Memoized<K, V, F> {
+    pub fn new(function: F) -> Memoized<K, V, F> {
+        Memoized {
+            function,
+            memo: Memo::new(),
+        }
+    }
+
+    pub fn bounded(function: F, capacity: usize) -> Memoized<K, V, F> {
+        Memoized {
+            function,
+            memo: Memo::bounded(capacity),
+        }
+    }
+
+    pub fn call(&mut self, key: K) -> &V {
+        self.memo.get_or_insert_with(key, &self.function)
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.memo.stats()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn compute_once() {
+        let calls = Cell::new(0);
+        let mut square = Memoized::new(|&n: &u64| {
+            calls.set(calls.get() + 1);
+            n * n
+        });
+        assert_eq!(*square.call(3), 9);
+        assert_eq!(*square.call(3), 9);
+        assert_eq!(*square.call(4), 16);
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            square.stats(),
+            Stats {
+                hits: 1,
+                misses: 2,
+                evictions: 0
+            }
+        );
+    }
+
+    #[test]
+    fn recursive() {
+        fn fibonacci(memo: &mut Memo<u64, u64>, &n: &u64) -> u64 {
+            match n {
+                0 | 1 => n,
+                _ => memo.recurse(n - 1, fibonacci) + memo.recurse(n - 2, fibonacci),
+            }
+        }
+        let mut memo = Memo::new();
+        assert_eq!(memo.recurse(90, fibonacci), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.stats().misses, 91);
+        assert_eq!(memo.stats().hits, 88);
+    }
+
+    #[test]
+    fn bound() {
+        let mut memo = Memo::bounded(2);
+        for key in [1, 2, 3, 1] {
+            memo.get_or_insert_with(key, |&key| key * 10);
+        }
+        assert_eq!(memo.len(), 2);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 0,
+                misses: 4,
+                evictions: 2
+            }
+        );
+        assert_eq!(*memo.get_or_insert_with(3, |_| 0), 30);
+        assert_eq!(memo.stats().hits, 1);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+}
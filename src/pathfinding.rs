@@ -0,0 +1,316 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// costs start from C::default() at the start node, edges are expected to be non-negative
+pub trait Cost: Copy + Ord + Default + Add<Output = Self> {}
+
+impl<C: Copy + Ord + Default + Add<Output = C>> Cost for C {}
+
+// every node a search reached with its cost and the node it was reached from
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    start: N,
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Cost> Search<N, C> {
+    fn new(start: N) -> Search<N, C> {
+        Search {
+            costs: HashMap::from([(start.clone(), C::default())]),
+            parents: HashMap::new(),
+            start,
+        }
+    }
+
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.costs.contains_key(node)
+    }
+
+    // the start node included, in no particular order
+    pub fn reached(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(node, &cost)| (node, cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.costs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    // from the start to `node`, both included
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// number of steps to every node reachable from `start`
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((node, steps)) = queue.pop_front() {
+        for neighbour in neighbours(&node) {
+            if !search.contains(&neighbour) {
+                search.costs.insert(neighbour.clone(), steps + 1);
+                search.parents.insert(neighbour.clone(), node.clone());
+                queue.push_back((neighbour, steps + 1));
+            }
+        }
+    }
+    search
+}
+
+// cheapest cost to every node reachable from `start`
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, _) = best_first(start, successors, |_| C::default(), |_| false);
+    search
+}
+
+// cheapest path from `start` to the first goal with its cost, `heuristic` must never
+// overestimate the remaining cost
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, goal) = best_first(start, successors, heuristic, is_goal);
+    let goal = goal?;
+    Some((search.path_to(&goal)?, search.cost(&goal)?))
+}
+
+// explores by lowest cost plus heuristic until a goal is taken from the queue
+fn best_first<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Search<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone());
+    // nodes live in a list so the heap only orders (estimate, cost, index), the index breaks
+    // ties in insertion order
+    let mut nodes = vec![start.clone()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if search.cost(&node).is_some_and(|best| cost > best) {
+            continue;
+        }
+        if is_goal(&node) {
+            return (search, Some(node));
+        }
+        for (successor, step) in successors(&node) {
+            let next = cost + step;
+            if search.cost(&successor).is_some_and(|best| best <= next) {
+                continue;
+            }
+            search.costs.insert(successor.clone(), next);
+            search.parents.insert(successor.clone(), node.clone());
+            queue.push(Reverse((next + heuristic(&successor), next, nodes.len())));
+            nodes.push(successor);
+        }
+    }
+    (search, None)
+}
+
+// every cheapest path from `start` to the goals nearest to it, with their cost
+pub fn all_shortest_paths<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<Vec<N>>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut parents: HashMap<N, Vec<N>> = HashMap::new();
+    let mut nodes = vec![start];
+    let mut queue = BinaryHeap::from([Reverse((C::default(), 0))]);
+    let mut goals = Vec::new();
+    let mut goal_cost = None;
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        let node = nodes[index].clone();
+        if cost > costs[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            goal_cost = Some(cost);
+            goals.push(node);
+            continue;
+        }
+        for (successor, step) in successors(&node) {
+            let next = cost + step;
+            match costs.get(&successor) {
+                Some(&best) if best < next => {}
+                Some(&best) if best == next => {
+                    parents.entry(successor).or_default().push(node.clone());
+                }
+                _ => {
+                    costs.insert(successor.clone(), next);
+                    parents.insert(successor.clone(), vec![node.clone()]);
+                    queue.push(Reverse((next, nodes.len())));
+                    nodes.push(successor);
+                }
+            }
+        }
+    }
+
+    // walk back through every parent, paths are built from the goal and reversed at the end
+    let mut paths = Vec::new();
+    let mut stack: Vec<Vec<N>> = goals.into_iter().map(|goal| vec![goal]).collect();
+    while let Some(path) = stack.pop() {
+        match parents.get(path.last().unwrap()) {
+            Some(node_parents) => {
+                for parent in node_parents.iter().rev() {
+                    let mut longer = path.clone();
+                    longer.push(parent.clone());
+                    stack.push(longer);
+                }
+            }
+            None => {
+                let mut path = path;
+                path.reverse();
+                paths.push(path);
+            }
+        }
+    }
+    Some((paths, goal_cost?))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+#######
+#S..#.#
+#.#.#.#
+#.#...#
+#...#E#
+#######";
+
+    fn maze() -> (Grid<char>, Point, Point) {
+        let grid = Grid::chars(0, MAZE).unwrap();
+        let start = grid.position(|&c| c == 'S').unwrap();
+        let end = grid.position(|&c| c == 'E').unwrap();
+        (grid, start, end)
+    }
+
+    fn open(grid: &Grid<char>, point: Point) -> impl Iterator<Item = Point> + '_ {
+        grid.neighbours4(point).filter(|&next| grid[next] != '#')
+    }
+
+    #[test]
+    fn breadth_first() {
+        let (grid, start, end) = maze();
+        let search = bfs(start, |&point| open(&grid, point));
+        assert_eq!(search.cost(&end), Some(7));
+        assert_eq!(search.cost(&(5, 1)), Some(8));
+        assert_eq!(search.cost(&(0, 0)), None);
+        assert_eq!(search.len(), 15);
+        let path = search.path_to(&end).unwrap();
+        assert_eq!((path[0], path[7]), (start, end));
+        assert!(
+            path.windows(2)
+                .all(|step| grid.neighbours4(step[0]).any(|next| next == step[1]))
+        );
+        assert_eq!(search.path_to(&start), Some(vec![start]));
+    }
+
+    #[test]
+    fn weighted() {
+        // the direct edge is more expensive than the detour
+        let edges = HashMap::from([
+            ('a', vec![('b', 10), ('c', 1)]),
+            ('c', vec![('d', 2)]),
+            ('d', vec![('b', 3)]),
+        ]);
+        let successors = |node: &char| edges.get(node).cloned().unwrap_or_default();
+        let search = dijkstra('a', successors);
+        assert_eq!(search.cost(&'b'), Some(6));
+        assert_eq!(search.path_to(&'b'), Some(vec!['a', 'c', 'd', 'b']));
+        assert_eq!(search.cost(&'e'), None);
+
+        let found = astar('a', successors, |_| 0, |&node| node == 'b');
+        assert_eq!(found, Some((vec!['a', 'c', 'd', 'b'], 6)));
+        assert_eq!(astar('a', successors, |_| 0, |&node| node == 'e'), None);
+    }
+
+    #[test]
+    fn heuristic() {
+        let (grid, start, end) = maze();
+        let manhattan = |&(x, y): &Point| x.abs_diff(end.0) + y.abs_diff(end.1);
+        let steps = |&point: &Point| open(&grid, point).map(|next| (next, 1));
+        let (path, cost) = astar(start, steps, manhattan, |&point| point == end).unwrap();
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+    }
+
+    #[test]
+    fn every_shortest_path() {
+        let grid = Grid::chars(0, "...\n...\n...").unwrap();
+        let steps = |&point: &Point| grid.neighbours4(point).map(|next| (next, 1));
+        let (paths, cost) = all_shortest_paths((0, 0), steps, |&point| point == (2, 2)).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(paths.len(), 6);
+        assert_eq!(paths[0], [(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)]);
+        assert!(paths.iter().all(|path| path.len() == 5));
+
+        // both corners are 2 steps away
+        let corners = |&point: &Point| point == (2, 0) || point == (0, 2);
+        let (paths, _) = all_shortest_paths((0, 0), steps, corners).unwrap();
+        assert_eq!(paths.len(), 2);
+
+        let (grid, start, _) = maze();
+        let walls = |&point: &Point| open(&grid, point).map(|next| (next, 1));
+        assert_eq!(
+            all_shortest_paths(start, walls, |_| false),
+            None::<(_, usize)>
+        );
+    }
+}
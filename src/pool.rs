@@ -0,0 +1,161 @@
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+pub fn default_workers() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// runs `f` on every job with `workers` threads, results keep the order of `jobs`
+// and a panicking job only loses its own result
+pub fn run<T, R, F>(jobs: &[T], workers: usize, f: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<Result<R, String>>>> =
+        jobs.iter().map(|_| Mutex::new(None)).collect();
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(index) else {
+                        break;
+                    };
+                    let result =
+                        panic::catch_unwind(AssertUnwindSafe(|| f(job))).map_err(panic_message);
+                    *results[index].lock().unwrap() = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| result.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+// long-lived workers for jobs that arrive one at a time, a panicking job is reported on stderr
+// and its worker moves on to the next one
+#[derive(Debug)]
+pub struct ThreadPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl ThreadPool {
+    pub fn new(workers: usize) -> ThreadPool {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || {
+                    loop {
+                        let job = receiver.lock().unwrap().recv();
+                        // the sender is gone, the pool is being dropped
+                        let Ok(job) = job else {
+                            break;
+                        };
+                        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(job)) {
+                            eprintln!("job {}", panic_message(payload));
+                        }
+                    }
+                })
+            })
+            .collect();
+        ThreadPool {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        if let Some(sender) = &self.sender {
+            sender.send(Box::new(job)).unwrap();
+        }
+    }
+}
+
+// waits for the queued jobs to finish
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown reason".to_owned(),
+        },
+    };
+    format!("panicked: {message}")
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_job_order() {
+        let jobs: Vec<u64> = (0..20).collect();
+        let results = run(&jobs, 4, |&job| job * job);
+        let expected: Vec<Result<u64, String>> = jobs.iter().map(|job| Ok(job * job)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let jobs = [1, 2, 3];
+        let results = run(&jobs, 2, |&job| {
+            if job == 2 {
+                panic!("job {job} failed");
+            }
+            job
+        });
+        assert_eq!(results[0], Ok(1));
+        assert_eq!(results[1], Err("panicked: job 2 failed".to_string()));
+        assert_eq!(results[2], Ok(3));
+    }
+
+    #[test]
+    fn thread_pool() {
+        let (sender, receiver) = mpsc::channel();
+        let pool = ThreadPool::new(3);
+        for job in 0..10u32 {
+            let sender = sender.clone();
+            pool.execute(move || {
+                if job == 4 {
+                    panic!("job {job} failed");
+                }
+                sender.send(job).unwrap();
+            });
+        }
+        drop(pool);
+        drop(sender);
+        let mut done: Vec<u32> = receiver.iter().collect();
+        done.sort();
+        assert_eq!(done, [0, 1, 2, 3, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn no_jobs() {
+        let results = run(&[] as &[u8], 0, |&job| job);
+        assert!(results.is_empty());
+    }
+}
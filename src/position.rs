@@ -0,0 +1,155 @@
+use crate::grid::Point;
+use Direction::{Down, DownLeft, DownRight, Left, Right, Up, UpLeft, UpRight};
+use std::ops::Add;
+
+// screen orientation like Grid: x grows to the right and y grows downwards,
+// so Up is the previous line of the input
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub const fn new(x: usize, y: usize) -> Position {
+        Position { x, y }
+    }
+
+    // None when the step would leave the width x height area starting at the origin
+    pub fn step_within(
+        self,
+        direction: Direction,
+        (width, height): (usize, usize),
+    ) -> Option<Position> {
+        (self + direction).filter(|next| next.x < width && next.y < height)
+    }
+}
+
+// None when the step goes below zero on either axis
+impl Add<Direction> for Position {
+    type Output = Option<Position>;
+
+    fn add(self, direction: Direction) -> Option<Position> {
+        let (dx, dy) = direction.offset();
+        Some(Position {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl From<Point> for Position {
+    fn from((x, y): Point) -> Position {
+        Position { x, y }
+    }
+}
+
+impl From<Position> for Point {
+    fn from(position: Position) -> Point {
+        (position.x, position.y)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    // clockwise starting from Up
+    pub const ALL: [Direction; 8] = [Up, UpRight, Right, DownRight, Down, DownLeft, Left, UpLeft];
+    pub const ORTHOGONAL: [Direction; 4] = [Up, Right, Down, Left];
+    pub const DIAGONAL: [Direction; 4] = [UpRight, DownRight, DownLeft, UpLeft];
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Up => (0, -1),
+            UpRight => (1, -1),
+            Right => (1, 0),
+            DownRight => (1, 1),
+            Down => (0, 1),
+            DownLeft => (-1, 1),
+            Left => (-1, 0),
+            UpLeft => (-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.eighths() % 2 == 1
+    }
+
+    // a quarter turn, diagonals stay diagonal
+    pub fn clockwise(self) -> Direction {
+        self.turn(2)
+    }
+
+    pub fn counter_clockwise(self) -> Direction {
+        self.turn(6)
+    }
+
+    // an eighth of a turn, so orthogonal and diagonal directions alternate
+    pub fn clockwise_eighth(self) -> Direction {
+        self.turn(1)
+    }
+
+    pub fn counter_clockwise_eighth(self) -> Direction {
+        self.turn(7)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn(4)
+    }
+
+    fn eighths(self) -> usize {
+        Direction::ALL
+            .iter()
+            .position(|&direction| direction == self)
+            .unwrap()
+    }
+
+    fn turn(self, eighths: usize) -> Direction {
+        Direction::ALL[(self.eighths() + eighths) % 8]
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate() {
+        assert_eq!(Up.clockwise(), Right);
+        assert_eq!(Left.clockwise(), Up);
+        assert_eq!(Up.counter_clockwise(), Left);
+        assert_eq!(UpLeft.clockwise(), UpRight);
+        assert_eq!(Up.clockwise_eighth(), UpRight);
+        assert_eq!(Up.counter_clockwise_eighth(), UpLeft);
+        assert_eq!(DownLeft.opposite(), UpRight);
+        for direction in Direction::ALL {
+            assert_eq!(direction.clockwise().counter_clockwise(), direction);
+            assert_eq!(direction.opposite().opposite(), direction);
+            let (dx, dy) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-dx, -dy));
+            assert_eq!(direction.is_diagonal(), dx != 0 && dy != 0);
+        }
+    }
+
+    #[test]
+    fn step() {
+        let origin = Position::new(0, 0);
+        assert_eq!(origin + Down, Some(Position::new(0, 1)));
+        assert_eq!(origin + Up, None);
+        assert_eq!(origin + Left, None);
+        assert_eq!(Position::new(2, 2) + UpLeft, Some(Position::new(1, 1)));
+        assert_eq!(Position::new(1, 0).step_within(Right, (2, 1)), None);
+        assert_eq!(Position::new(1, 0).step_within(Left, (2, 1)), Some(origin));
+        assert_eq!(Point::from(Position::new(3, 4)), (3, 4));
+    }
+}
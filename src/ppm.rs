@@ -0,0 +1,201 @@
+use crate::grid::Grid;
+use crate::render::{Colour, Glyph};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+// how glyph colours become pixels, a glyph takes its background colour, then its foreground
+// colour and `empty` when it has neither
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub empty: Rgb,
+    // in the order the colours are declared
+    pub colours: [Rgb; 9],
+}
+
+impl Palette {
+    pub const DEFAULT: Palette = Palette {
+        empty: Rgb(24, 24, 24),
+        colours: [
+            Rgb(0, 0, 0),
+            Rgb(205, 49, 49),
+            Rgb(13, 188, 121),
+            Rgb(229, 229, 16),
+            Rgb(36, 114, 200),
+            Rgb(188, 63, 188),
+            Rgb(17, 168, 205),
+            Rgb(229, 229, 229),
+            Rgb(102, 102, 102),
+        ],
+    };
+
+    pub const fn with(self, colour: Colour, rgb: Rgb) -> Palette {
+        let mut colours = self.colours;
+        colours[colour as usize] = rgb;
+        Palette { colours, ..self }
+    }
+
+    pub const fn with_empty(self, empty: Rgb) -> Palette {
+        Palette { empty, ..self }
+    }
+
+    pub fn rgb(&self, glyph: &Glyph) -> Rgb {
+        glyph
+            .background
+            .or(glyph.foreground)
+            .map_or(self.empty, |colour| self.colours[colour as usize])
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::DEFAULT
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    // every cell becomes a `scale` x `scale` square
+    pub fn from_canvas(canvas: &Grid<Glyph>, palette: &Palette, scale: usize) -> Image {
+        assert!(scale > 0, "a cell needs at least one pixel");
+        let (width, height) = (canvas.width() * scale, canvas.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in canvas.rows() {
+            let line: Vec<Rgb> = row
+                .iter()
+                .flat_map(|glyph| [palette.rgb(glyph)].repeat(scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    // binary PPM, a text header followed by three bytes per pixel
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.reserve(self.pixels.len() * 3);
+        for &Rgb(r, g, b) in &self.pixels {
+            bytes.extend_from_slice(&[r, g, b]);
+        }
+        bytes
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_ppm())
+            .map_err(|error| format!("can't write {}: {error}", path.display()))
+    }
+}
+
+// writes frames as <dir>/<prefix>_0000.ppm, <dir>/<prefix>_0001.ppm and so on
+#[derive(Debug)]
+pub struct FrameWriter {
+    dir: PathBuf,
+    prefix: String,
+    scale: usize,
+    count: usize,
+}
+
+impl FrameWriter {
+    pub fn new(dir: &Path, prefix: &str, scale: usize) -> Result<FrameWriter, String> {
+        fs::create_dir_all(dir)
+            .map_err(|error| format!("can't create {}: {error}", dir.display()))?;
+        Ok(FrameWriter {
+            dir: dir.to_owned(),
+            prefix: prefix.to_owned(),
+            scale,
+            count: 0,
+        })
+    }
+
+    pub fn write(&mut self, canvas: &Grid<Glyph>, palette: &Palette) -> Result<PathBuf, String> {
+        let path = self
+            .dir
+            .join(format!("{}_{:04}.ppm", self.prefix, self.count));
+        Image::from_canvas(canvas, palette, self.scale).save(&path)?;
+        self.count += 1;
+        Ok(path)
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn canvas() -> Grid<Glyph> {
+        Grid::new(
+            2,
+            1,
+            vec![
+                Glyph::new('.'),
+                Glyph::new('#').fg(Colour::Red).bg(Colour::Blue),
+            ],
+        )
+    }
+
+    #[test]
+    fn scaled_pixels() {
+        let palette = Palette::DEFAULT.with(Colour::Blue, Rgb(0, 0, 255));
+        let image = Image::from_canvas(&canvas(), &palette, 2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.pixel(1, 1), palette.empty);
+        assert_eq!(image.pixel(2, 0), Rgb(0, 0, 255));
+        assert_eq!(image.pixel(3, 1), Rgb(0, 0, 255));
+    }
+
+    #[test]
+    fn binary_ppm() {
+        let palette = Palette::DEFAULT
+            .with_empty(Rgb(1, 2, 3))
+            .with(Colour::Blue, Rgb(4, 5, 6));
+        let bytes = Image::from_canvas(&canvas(), &palette, 1).to_ppm();
+        let mut expected = b"P6\n2 1\n255\n".to_vec();
+        expected.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn numbered_frames() {
+        let dir = env::temp_dir().join(format!("day1q1-frames-{}", process::id()));
+        let mut writer = FrameWriter::new(&dir, "day6", 3).unwrap();
+        writer.write(&canvas(), &Palette::DEFAULT).unwrap();
+        let path = writer.write(&canvas(), &Palette::DEFAULT).unwrap();
+        assert_eq!(path, dir.join("day6_0001.ppm"));
+        assert_eq!(writer.count(), 2);
+        let bytes = fs::read(dir.join("day6_0000.ppm")).unwrap();
+        assert!(bytes.starts_with(b"P6\n6 3\n255\n"));
+        assert_eq!(bytes.len(), "P6\n6 3\n255\n".len() + 6 * 3 * 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
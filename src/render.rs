@@ -0,0 +1,219 @@
+use crate::grid::Grid;
+use crate::input::normalize;
+use crate::ppm::Palette;
+use crate::solution::Solution;
+use std::env;
+use std::io::{self, IsTerminal};
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    // the ANSI code of the colour as a foreground, a background is 10 more
+    fn code(self) -> u8 {
+        match self {
+            Colour::Black => 30,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+            Colour::Grey => 90,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RenderOptions {
+    // also draw every n-th step of a simulation, not just the final state
+    pub every: Option<usize>,
+    pub colour: bool,
+    // write the frames as PPM images in this directory instead of drawing them
+    pub ppm: Option<String>,
+    // pixels per cell side in the images
+    pub scale: usize,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            every: None,
+            colour: true,
+            ppm: None,
+            scale: 4,
+        }
+    }
+}
+
+// colours that are easy to tell apart, for things like crops and frequencies
+pub const PALETTE: [Colour; 6] = [
+    Colour::Red,
+    Colour::Green,
+    Colour::Yellow,
+    Colour::Blue,
+    Colour::Magenta,
+    Colour::Cyan,
+];
+
+pub fn palette(key: usize) -> Colour {
+    PALETTE[key % PALETTE.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    pub symbol: char,
+    pub foreground: Option<Colour>,
+    pub background: Option<Colour>,
+}
+
+impl Glyph {
+    pub const fn new(symbol: char) -> Glyph {
+        Glyph {
+            symbol,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    pub const fn fg(self, colour: Colour) -> Glyph {
+        Glyph {
+            foreground: Some(colour),
+            ..self
+        }
+    }
+
+    pub const fn bg(self, colour: Colour) -> Glyph {
+        Glyph {
+            background: Some(colour),
+            ..self
+        }
+    }
+
+    fn style(&self) -> Option<String> {
+        let codes: Vec<String> = [
+            self.foreground.map(Colour::code),
+            self.background.map(|colour| colour.code() + 10),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|code| code.to_string())
+        .collect();
+        (!codes.is_empty()).then(|| format!("\x1b[{}m", codes.join(";")))
+    }
+}
+
+// one line per row, without colour only the symbols are kept so a day has to pick symbols
+// that still make sense on their own
+pub fn draw(canvas: &Grid<Glyph>, colour: bool) -> String {
+    let mut result = String::new();
+    for row in canvas.rows() {
+        let mut current = None;
+        for glyph in row {
+            let style = if colour { glyph.style() } else { None };
+            if style != current {
+                if current.is_some() {
+                    result.push_str(RESET);
+                }
+                if let Some(style) = &style {
+                    result.push_str(style);
+                }
+                current = style;
+            }
+            result.push(glyph.symbol);
+        }
+        if current.is_some() {
+            result.push_str(RESET);
+        }
+        result.push('\n');
+    }
+    result
+}
+
+// colour is only used on a terminal and never when NO_COLOR is set
+pub fn colour_supported() -> bool {
+    let disabled = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !disabled && io::stdout().is_terminal()
+}
+
+// whether a simulation draws the state before `step`
+pub fn is_frame(every: Option<usize>, step: usize) -> bool {
+    every.is_some_and(|every| step.is_multiple_of(every))
+}
+
+pub trait Render: Solution {
+    // the colours of the exported images
+    const PALETTE: Palette = Palette::DEFAULT;
+
+    // calls `frame` with the final state, simulations also call it every `every` steps
+    // before that
+    fn render(
+        input: &Self::Input,
+        every: Option<usize>,
+        frame: &mut dyn FnMut(&Grid<Glyph>),
+    ) -> Result<(), String>;
+}
+
+pub type Renderer =
+    fn(&str, Option<usize>, &mut dyn FnMut(&Grid<Glyph>, &Palette)) -> Result<(), String>;
+
+pub fn render<R: Render>(
+    input: &str,
+    every: Option<usize>,
+    frame: &mut dyn FnMut(&Grid<Glyph>, &Palette),
+) -> Result<(), String> {
+    let parsed = R::parse(&normalize(input)).map_err(|error| error.to_string())?;
+    R::render(&parsed, every, &mut |canvas| frame(canvas, &R::PALETTE))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn canvas() -> Grid<Glyph> {
+        Grid::new(
+            3,
+            2,
+            vec![
+                Glyph::new('#'),
+                Glyph::new('A').fg(Colour::Red),
+                Glyph::new('A').fg(Colour::Red),
+                Glyph::new('.'),
+                Glyph::new('^').fg(Colour::Yellow).bg(Colour::Blue),
+                Glyph::new('.'),
+            ],
+        )
+    }
+
+    #[test]
+    fn without_colour() {
+        assert_eq!(draw(&canvas(), false), "#AA\n.^.\n");
+    }
+
+    #[test]
+    fn with_colour() {
+        assert_eq!(
+            draw(&canvas(), true),
+            "#\x1b[31mAA\x1b[0m\n.\x1b[33;44m^\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn palette_wraps() {
+        assert_eq!(palette(0), Colour::Red);
+        assert_eq!(palette(PALETTE.len() + 1), Colour::Green);
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Format, String> {
+        match value {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {value:?}, expected text or json")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PartResult {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String, String>,
+    pub time: Duration,
+}
+
+impl PartResult {
+    pub fn run(day: u8, part: u8, solve: impl FnOnce() -> Result<String, String>) -> PartResult {
+        let start = Instant::now();
+        let answer = solve();
+        PartResult {
+            day,
+            part,
+            answer,
+            time: start.elapsed(),
+        }
+    }
+
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Text => self.to_text(),
+            Format::Json => self.to_json(),
+        }
+    }
+
+    fn to_text(&self) -> String {
+        match &self.answer {
+            Ok(answer) => format!("day {} part {}: {answer}", self.day, self.part),
+            Err(error) => format!("day {} part {}: error: {error}", self.day, self.part),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (json_string(answer), "null".to_owned()),
+            Err(error) => ("null".to_owned(), json_string(error)),
+        };
+        format!(
+            r#"{{"day":{},"part":{},"answer":{answer},"time_ms":{:.3},"error":{error}}}"#,
+            self.day,
+            self.part,
+            self.time.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_strings() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(
+            json_string("say \"hi\"\\\n\u{1}"),
+            r#""say \"hi\"\\\n\u0001""#
+        );
+    }
+
+    #[test]
+    fn format_results() {
+        let answer = PartResult {
+            day: 3,
+            part: 1,
+            answer: Ok("161".into()),
+            time: Duration::from_micros(1500),
+        };
+        assert_eq!(answer.format(Format::Text), "day 3 part 1: 161");
+        assert_eq!(
+            answer.format(Format::Json),
+            r#"{"day":3,"part":1,"answer":"161","time_ms":1.500,"error":null}"#
+        );
+        let error = PartResult {
+            answer: Err("day 3, line 1, column 1: bad \"x\"".into()),
+            ..answer
+        };
+        assert_eq!(
+            error.format(Format::Json),
+            r#"{"day":3,"part":1,"answer":null,"time_ms":1.500,"error":"day 3, line 1, column 1: bad \"x\""}"#
+        );
+    }
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Format::parse("json"), Ok(Format::Json));
+        assert!(Format::parse("yaml").is_err());
+    }
+}
@@ -0,0 +1,983 @@
+use crate::bench::{self, BenchOptions, Timings};
+use crate::check::{self, Answers, Outcome};
+use crate::error::ParseError;
+use crate::fetch::{self, CurlHttp, Fetched, Fetcher, Throttle};
+use crate::generate::{self, Shape};
+use crate::input::{self, InputOptions, Source};
+use crate::pool;
+use crate::ppm::FrameWriter;
+use crate::render::{self, Render, RenderOptions, Renderer};
+use crate::report::{Format, PartResult};
+use crate::scaffold;
+use crate::server::{self, Server};
+use crate::solution::{Solution, SolveError, solve, try_solve};
+use crate::{
+    day1, day2, day3, day4, day4_part2, day5, day5_part2, day6, day6_part2, day7, day7_part2, day8,
+    day8_part2, day9, day9_part2, day10, day10_part2, day11, day11_part2, day12, day13, template,
+};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const USAGE: &str = "usage:
+    day1q1 <day> [part] [input] [--format text|json]
+    day1q1 --day <day> [--part <part>] [--input <path>] [--input-dir <dir>]
+    day1q1 --all [--workers <n>] [--format text|json]
+    day1q1 <module> [--input <path>]    e.g. day6_part2, template
+    day1q1 --check [--record] [--answers <path>] [day] [part]
+    day1q1 --bench [--runs <n>] [--warmup <n>] [day]
+    day1q1 <day> --render [--every <n>] [--no-colour]    draws the final state of the day
+    day1q1 <day> --ppm <dir> [--scale <k>] [--every <n>]    writes it as PPM images instead
+    day1q1 new-day <day>    creates src/day<day>.rs from the template
+    day1q1 generate <day> [--size <width>x<height>] [--seed <n>]    prints a random input
+    day1q1 serve [--address <host:port>] [--workers <n>]    answers over HTTP
+    day1q1 fetch --day <day> [--input-dir <dir>] [--session-file <path>]    downloads an input
+
+input is read from <dir>/input_day<day>.txt, where <dir> is --input-dir,
+$AOC_INPUT_DIR or artifacts/input_files; use - as the input path for stdin
+--check compares answers with artifacts/answers.txt, --record adds the missing ones
+--render draws in colour on a terminal unless NO_COLOR is set, --every also draws every n-th
+step of a simulation, --ppm writes <dir>/day<day>_0000.ppm onwards with k x k pixels per cell
+serve listens on 127.0.0.1:8080 by default, GET /days lists the days and
+POST /day/<day>/part/<part> with the input as the body returns the answer as JSON
+fetch saves the input where <day> reads it, never downloads it again and waits 5s between
+downloads, the session token is $AOC_SESSION or the contents of .aoc_session";
+
+#[derive(Debug)]
+pub struct Module {
+    pub name: &'static str,
+    pub day: u8,
+    pub main: fn(&str) -> Result<(), ParseError>,
+}
+
+impl Module {
+    const fn new(name: &'static str, day: u8, main: fn(&str) -> Result<(), ParseError>) -> Self {
+        Module { name, day, main }
+    }
+}
+
+pub const MODULES: &[Module] = &[
+    Module::new("template", 1, template::main),
+    Module::new("day1", 1, day1::main),
+    Module::new("day2", 2, day2::main),
+    Module::new("day3", 3, day3::main),
+    Module::new("day4", 4, day4::main),
+    Module::new("day4_part2", 4, day4_part2::main),
+    Module::new("day5", 5, day5::main),
+    Module::new("day5_part2", 5, day5_part2::main),
+    Module::new("day6", 6, day6::main),
+    Module::new("day6_part2", 6, day6_part2::main),
+    Module::new("day7", 7, day7::main),
+    Module::new("day7_part2", 7, day7_part2::main),
+    Module::new("day8", 8, day8::main),
+    Module::new("day8_part2", 8, day8_part2::main),
+    Module::new("day9", 9, day9::main),
+    Module::new("day9_part2", 9, day9_part2::main),
+    Module::new("day10", 10, day10::main),
+    Module::new("day10_part2", 10, day10_part2::main),
+    Module::new("day11", 11, day11::main),
+    Module::new("day11_part2", 11, day11_part2::main),
+    Module::new("day12", 12, day12::main),
+    Module::new("day13", 13, day13::main),
+];
+
+#[derive(Debug)]
+pub struct Day {
+    pub day: u8,
+    pub solve: fn(&str, u8) -> Result<String, String>,
+    // like `solve`, but keeps the kind of error
+    pub try_solve: fn(&str, u8) -> Result<String, SolveError>,
+    pub bench: fn(&str, &BenchOptions) -> Result<Timings, String>,
+    pub render: Option<Renderer>,
+}
+
+impl Day {
+    const fn new<S: Solution>(day: u8) -> Self {
+        Day {
+            day,
+            solve: solve::<S>,
+            try_solve: try_solve::<S>,
+            bench: bench::bench::<S>,
+            render: None,
+        }
+    }
+
+    const fn rendered<S: Render>(day: u8) -> Self {
+        Day {
+            render: Some(render::render::<S>),
+            ..Day::new::<S>(day)
+        }
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::rendered::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::rendered::<day8::Day8>(8),
+    Day::rendered::<day9::Day9>(9),
+    Day::rendered::<day10::Day10>(10),
+    Day::rendered::<day11::Day11>(11),
+    Day::rendered::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Selector {
+    Day { day: u8, part: Option<u8> },
+    Module(String),
+    All,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Mode {
+    Solve,
+    Check { answers: String, record: bool },
+    Bench(BenchOptions),
+    Render(RenderOptions),
+    NewDay,
+    Generate { shape: Option<Shape>, seed: u64 },
+    Serve { address: String },
+    Fetch { session_file: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub selector: Selector,
+    pub input: InputOptions,
+    pub mode: Mode,
+    pub format: Format,
+    pub workers: Option<usize>,
+}
+
+impl Args {
+    pub fn parse(args: &[String]) -> Result<Args, String> {
+        match args.split_first() {
+            Some((command, rest)) if command == "new-day" => return Self::parse_new_day(rest),
+            Some((command, rest)) if command == "generate" => return Self::parse_generate(rest),
+            Some((command, rest)) if command == "serve" => return Self::parse_serve(rest),
+            Some((command, rest)) if command == "fetch" => return Self::parse_fetch(rest),
+            _ => {}
+        }
+        let mut day = None;
+        let mut part = None;
+        let mut input = None;
+        let mut dir = None;
+        let mut module = None;
+        let mut check = false;
+        let mut record = false;
+        let mut answers = None;
+        let mut bench = None;
+        let mut render = None;
+        let mut format = Format::Text;
+        let mut all = false;
+        let mut workers = None;
+        let mut positional = Vec::new();
+
+        let mut args_iter = args.iter();
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "--day" | "-d" => day = Some(Self::parse_number("day", args_iter.next())?),
+                "--part" | "-p" => part = Some(Self::parse_number("part", args_iter.next())?),
+                "--input" | "-i" => {
+                    let path = args_iter.next().ok_or("missing value for --input")?;
+                    input = Some(path.clone())
+                }
+                "--input-dir" => {
+                    let path = args_iter.next().ok_or("missing value for --input-dir")?;
+                    dir = Some(path.clone())
+                }
+                "--format" | "-f" => {
+                    let value = args_iter.next().ok_or("missing value for --format")?;
+                    format = Format::parse(value)?
+                }
+                "--all" | "-a" => all = true,
+                "--workers" | "-w" => {
+                    workers = Some(Self::parse_number("workers", args_iter.next())?)
+                }
+                "--check" => check = true,
+                "--bench" => bench = Some(bench.unwrap_or_default()),
+                "--runs" => {
+                    let runs = Self::parse_number("runs", args_iter.next())?;
+                    if runs == 0 {
+                        return Err("--runs needs at least 1 run".into());
+                    }
+                    bench = Some(BenchOptions {
+                        runs,
+                        ..bench.unwrap_or_default()
+                    })
+                }
+                "--warmup" => {
+                    let warmup = Self::parse_number("warmup", args_iter.next())?;
+                    bench = Some(BenchOptions {
+                        warmup,
+                        ..bench.unwrap_or_default()
+                    })
+                }
+                "--render" => render = Some(render.unwrap_or_default()),
+                "--every" => {
+                    let every = Self::parse_number("every", args_iter.next())?;
+                    if every == 0 {
+                        return Err("--every needs at least 1 step".into());
+                    }
+                    render = Some(RenderOptions {
+                        every: Some(every),
+                        ..render.unwrap_or_default()
+                    })
+                }
+                "--no-colour" => {
+                    render = Some(RenderOptions {
+                        colour: false,
+                        ..render.unwrap_or_default()
+                    })
+                }
+                "--ppm" => {
+                    let dir = args_iter.next().ok_or("missing value for --ppm")?;
+                    render = Some(RenderOptions {
+                        ppm: Some(dir.clone()),
+                        ..render.unwrap_or_default()
+                    })
+                }
+                "--scale" => {
+                    let scale = Self::parse_number("scale", args_iter.next())?;
+                    if scale == 0 {
+                        return Err("--scale needs at least 1 pixel".into());
+                    }
+                    render = Some(RenderOptions {
+                        scale,
+                        ..render.unwrap_or_default()
+                    })
+                }
+                "--record" => record = true,
+                "--answers" => {
+                    let path = args_iter.next().ok_or("missing value for --answers")?;
+                    answers = Some(path.clone())
+                }
+                flag if flag.starts_with('-') && flag != input::STDIN => {
+                    return Err(format!("unknown option {flag}"));
+                }
+                value => positional.push(value),
+            }
+        }
+
+        let mut positional = positional.into_iter().peekable();
+        if day.is_none() {
+            match positional.next() {
+                Some(value) if value.starts_with(|c: char| c.is_ascii_digit()) => {
+                    day = Some(Self::parse_number("day", Some(value))?)
+                }
+                Some(value) => module = Some(value.to_owned()),
+                None => {}
+            }
+        }
+        if day.is_some()
+            && part.is_none()
+            && let Some(value) = positional.next_if(|value| *value != input::STDIN)
+        {
+            part = Some(Self::parse_number("part", Some(value))?);
+        }
+        if input.is_none() {
+            input = positional.next().map(str::to_owned);
+        }
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument {extra}"));
+        }
+
+        let mode = match (check || record, bench, render) {
+            (true, None, None) => Mode::Check {
+                answers: answers.unwrap_or_else(|| check::ANSWERS_PATH.to_owned()),
+                record,
+            },
+            (false, Some(options), None) => Mode::Bench(options),
+            (false, None, Some(options)) => Mode::Render(options),
+            (false, None, None) => Mode::Solve,
+            _ => return Err("--check, --bench and --render can't be combined".into()),
+        };
+        let selector = match (day, module, &mode) {
+            (None, None, _) if all => Selector::All,
+            _ if all => return Err("--all can't be combined with a day".into()),
+            (Some(day), None, _) => Selector::Day { day, part },
+            (None, Some(_), Mode::Check { .. } | Mode::Bench(_) | Mode::Render(_)) => {
+                return Err("--check, --bench and --render need a day, not a module".into());
+            }
+            (None, Some(name), _) => Selector::Module(name),
+            (None, None, Mode::Check { .. } | Mode::Bench(_)) => Selector::All,
+            _ => return Err("missing day".into()),
+        };
+        if let Mode::Render(_) = mode
+            && !matches!(selector, Selector::Day { part: None, .. })
+        {
+            return Err("--render needs a day without a part".into());
+        }
+        if selector == Selector::All && input.is_some() {
+            return Err("--input needs a day".into());
+        }
+        if workers.is_some() && (selector != Selector::All || mode != Mode::Solve) {
+            return Err("--workers needs --all".into());
+        }
+        if format == Format::Json
+            && (mode != Mode::Solve || matches!(selector, Selector::Module(_)))
+        {
+            return Err("--format json only works when solving days".into());
+        }
+        Ok(Args {
+            selector,
+            input: InputOptions { dir, path: input },
+            mode,
+            format,
+            workers,
+        })
+    }
+
+    fn parse_new_day(args: &[String]) -> Result<Args, String> {
+        let [day] = args else {
+            return Err("new-day needs exactly one day".into());
+        };
+        Ok(Self::command(
+            Self::parse_number("day", Some(day))?,
+            Mode::NewDay,
+        ))
+    }
+
+    fn parse_generate(args: &[String]) -> Result<Args, String> {
+        let mut day = None;
+        let mut shape = None;
+        let mut seed = generate::DEFAULT_SEED;
+        let mut args_iter = args.iter();
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "--size" | "-s" => {
+                    let value = args_iter.next().ok_or("missing value for --size")?;
+                    shape = Some(Shape::parse(value)?)
+                }
+                "--seed" => seed = Self::parse_number("seed", args_iter.next())?,
+                flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+                value if day.is_none() => day = Some(Self::parse_number("day", Some(value))?),
+                value => return Err(format!("unexpected argument {value}")),
+            }
+        }
+        let day = day.ok_or("generate needs a day")?;
+        Ok(Self::command(day, Mode::Generate { shape, seed }))
+    }
+
+    fn parse_serve(args: &[String]) -> Result<Args, String> {
+        let mut address = server::DEFAULT_ADDRESS.to_owned();
+        let mut workers = None;
+        let mut args_iter = args.iter();
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "--address" => {
+                    let value = args_iter.next().ok_or("missing value for --address")?;
+                    address = value.clone()
+                }
+                "--workers" | "-w" => {
+                    workers = Some(Self::parse_number("workers", args_iter.next())?)
+                }
+                flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+                value => return Err(format!("unexpected argument {value}")),
+            }
+        }
+        Ok(Args {
+            selector: Selector::All,
+            workers,
+            ..Self::command(0, Mode::Serve { address })
+        })
+    }
+
+    fn parse_fetch(args: &[String]) -> Result<Args, String> {
+        let mut day = None;
+        let mut dir = None;
+        let mut session_file = fetch::SESSION_FILE.to_owned();
+        let mut args_iter = args.iter();
+        while let Some(arg) = args_iter.next() {
+            match arg.as_str() {
+                "--day" | "-d" => day = Some(Self::parse_number("day", args_iter.next())?),
+                "--input-dir" => {
+                    let path = args_iter.next().ok_or("missing value for --input-dir")?;
+                    dir = Some(path.clone())
+                }
+                "--session-file" => {
+                    let path = args_iter.next().ok_or("missing value for --session-file")?;
+                    session_file = path.clone()
+                }
+                flag if flag.starts_with('-') => return Err(format!("unknown option {flag}")),
+                value if day.is_none() => day = Some(Self::parse_number("day", Some(value))?),
+                value => return Err(format!("unexpected argument {value}")),
+            }
+        }
+        let day = day.ok_or("fetch needs a day")?;
+        if !(1..=25).contains(&day) {
+            return Err(format!("invalid day {day}, puzzles go from 1 to 25"));
+        }
+        Ok(Args {
+            input: InputOptions { dir, path: None },
+            ..Self::command(day, Mode::Fetch { session_file })
+        })
+    }
+
+    fn command(day: u8, mode: Mode) -> Args {
+        Args {
+            selector: Selector::Day { day, part: None },
+            input: InputOptions::default(),
+            mode,
+            format: Format::Text,
+            workers: None,
+        }
+    }
+
+    fn parse_number<T: AsRef<str>, N: FromStr>(name: &str, value: Option<T>) -> Result<N, String> {
+        let value = value.ok_or(format!("missing value for {name}"))?;
+        value
+            .as_ref()
+            .parse()
+            .map_err(|_| format!("invalid {name} {:?}", value.as_ref()))
+    }
+}
+
+pub fn find_module(name: &str) -> Result<&'static Module, String> {
+    MODULES
+        .iter()
+        .find(|module| module.name == name)
+        .ok_or(format!("unknown module {name}"))
+}
+
+pub fn find_day(day: u8) -> Result<&'static Day, String> {
+    DAYS.iter().find(|entry| entry.day == day).ok_or(format!(
+        "unknown day {day}, available days are 1-{}",
+        DAYS.len()
+    ))
+}
+
+pub fn run(args: &Args) -> Result<(), String> {
+    match &args.mode {
+        Mode::Check { answers, record } => return run_check(args, answers, *record),
+        Mode::Bench(options) => return run_bench(args, options),
+        Mode::Render(options) => return run_render(args, options),
+        Mode::NewDay => return run_new_day(&args.selector),
+        Mode::Generate { shape, seed } => return run_generate(&args.selector, *shape, *seed),
+        Mode::Serve { address } => return run_serve(address, args.workers),
+        Mode::Fetch { session_file } => return run_fetch(args, session_file),
+        Mode::Solve => {}
+    }
+    match &args.selector {
+        Selector::Module(name) => {
+            let module = find_module(name)?;
+            let input = args.input.read(module.day)?;
+            (module.main)(&input::normalize(&input)).map_err(|error| error.to_string())?;
+        }
+        Selector::Day { day, part } => {
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+            let results = solve_day(find_day(*day)?, &args.input, &parts);
+            return report(&results, args.format);
+        }
+        Selector::All => {
+            let jobs: Vec<(&Day, u8)> = DAYS
+                .iter()
+                .flat_map(|entry| [(entry, 1), (entry, 2)])
+                .collect();
+            let workers = args.workers.unwrap_or_else(pool::default_workers);
+            let results: Vec<PartResult> = pool::run(&jobs, workers, |&(entry, part)| {
+                solve_day(entry, &args.input, &[part]).remove(0)
+            })
+            .into_iter()
+            .zip(&jobs)
+            .map(|(result, &(entry, part))| {
+                result.unwrap_or_else(|error| PartResult {
+                    day: entry.day,
+                    part,
+                    answer: Err(error),
+                    time: Duration::ZERO,
+                })
+            })
+            .collect();
+            return report(&results, args.format);
+        }
+    }
+    Ok(())
+}
+
+fn solve_day(entry: &Day, input: &InputOptions, parts: &[u8]) -> Vec<PartResult> {
+    let input = input.read(entry.day);
+    parts
+        .iter()
+        .map(|&part| {
+            PartResult::run(entry.day, part, || match &input {
+                Ok(input) => (entry.solve)(input, part),
+                Err(error) => Err(error.clone()),
+            })
+        })
+        .collect()
+}
+
+fn report(results: &[PartResult], format: Format) -> Result<(), String> {
+    for result in results {
+        println!("{}", result.format(format));
+    }
+    match results
+        .iter()
+        .filter(|result| result.answer.is_err())
+        .count()
+    {
+        0 => Ok(()),
+        1 => Err("1 part failed".into()),
+        failed => Err(format!("{failed} parts failed")),
+    }
+}
+
+fn selected_days(selector: &Selector) -> Result<Vec<&'static Day>, String> {
+    match selector {
+        Selector::Day { day, .. } => Ok(vec![find_day(*day)?]),
+        Selector::All => Ok(DAYS.iter().collect()),
+        Selector::Module(name) => Err(format!("{name} isn't a day")),
+    }
+}
+
+fn run_bench(args: &Args, options: &BenchOptions) -> Result<(), String> {
+    let mut rows = Vec::new();
+    for entry in selected_days(&args.selector)? {
+        let input = args.input.read(entry.day)?;
+        eprintln!("benchmarking day {}", entry.day);
+        rows.push((entry.day, (entry.bench)(&input, options)?));
+    }
+    print!("{}", bench::format_table(&rows));
+    Ok(())
+}
+
+fn run_render(args: &Args, options: &RenderOptions) -> Result<(), String> {
+    let Selector::Day { day, .. } = args.selector else {
+        return Err("--render needs a day".into());
+    };
+    let render = find_day(day)?
+        .render
+        .ok_or(format!("day {day} has nothing to render"))?;
+    let input = args.input.read(day)?;
+    let Some(dir) = &options.ppm else {
+        let colour = options.colour && render::colour_supported();
+        return render(&input, options.every, &mut |canvas, _| {
+            println!("{}", render::draw(canvas, colour))
+        });
+    };
+    let mut writer = FrameWriter::new(Path::new(dir), &format!("day{day}"), options.scale)?;
+    // the first failed write is kept and the frames after it are skipped
+    let mut written = Ok(());
+    render(&input, options.every, &mut |canvas, palette| {
+        if written.is_ok() {
+            written = writer.write(canvas, palette).map(|_| ());
+        }
+    })?;
+    written?;
+    println!("wrote {} frames to {dir}", writer.count());
+    Ok(())
+}
+
+fn run_new_day(selector: &Selector) -> Result<(), String> {
+    let Selector::Day { day, .. } = selector else {
+        return Err("new-day needs a day".into());
+    };
+    for path in scaffold::new_day(Path::new("."), *day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn run_generate(selector: &Selector, shape: Option<Shape>, seed: u64) -> Result<(), String> {
+    let Selector::Day { day, .. } = selector else {
+        return Err("generate needs a day".into());
+    };
+    print!("{}", generate::generate(*day, shape, seed)?);
+    Ok(())
+}
+
+fn run_serve(address: &str, workers: Option<usize>) -> Result<(), String> {
+    let server = Server::bind(address, workers.unwrap_or_else(pool::default_workers))?;
+    println!("listening on http://{}", server.local_addr()?);
+    server.run()
+}
+
+fn run_fetch(args: &Args, session_file: &str) -> Result<(), String> {
+    let Selector::Day { day, .. } = args.selector else {
+        return Err("fetch needs a day".into());
+    };
+    let Source::File(path) = args.input.resolve(day) else {
+        return Err("fetch can't write to stdin".into());
+    };
+    // no token is needed for an input that is already there
+    if path.exists() {
+        println!("{} is already there", path.display());
+        return Ok(());
+    }
+    let fetcher = Fetcher {
+        http: CurlHttp,
+        base_url: fetch::BASE_URL.to_owned(),
+        session: fetch::session(Path::new(session_file))?,
+        throttle: Throttle::new(path.parent().unwrap_or(Path::new(".")), fetch::MIN_INTERVAL),
+    };
+    match fetcher.fetch(day, &path)? {
+        Fetched::Cached => println!("{} is already there", path.display()),
+        Fetched::Downloaded => println!("wrote {}", path.display()),
+    }
+    Ok(())
+}
+
+fn run_check(args: &Args, answers_path: &str, record: bool) -> Result<(), String> {
+    let selected: Vec<(&Day, Vec<u8>)> = match &args.selector {
+        Selector::Day { day, part } => {
+            vec![(find_day(*day)?, part.map_or(vec![1, 2], |part| vec![part]))]
+        }
+        Selector::All => DAYS.iter().map(|entry| (entry, vec![1, 2])).collect(),
+        Selector::Module(name) => return Err(format!("can't check module {name}")),
+    };
+    let mut answers = Answers::load(answers_path)?;
+    let (mut failed, mut missing) = (0, 0);
+    for (entry, parts) in selected {
+        let input = args.input.read(entry.day);
+        for part in parts {
+            let day = entry.day;
+            let actual = input
+                .as_ref()
+                .map_err(String::clone)
+                .and_then(|input| (entry.solve)(input, part));
+            match actual {
+                Ok(actual) => {
+                    let outcome = Outcome::compare(answers.get(day, part), actual);
+                    println!("day {day} part {part}: {outcome}");
+                    match outcome {
+                        Outcome::Pass(_) => {}
+                        Outcome::Fail { .. } => failed += 1,
+                        Outcome::Missing(actual) => {
+                            missing += 1;
+                            if record {
+                                answers.insert(day, part, actual);
+                            }
+                        }
+                    }
+                }
+                Err(error) => {
+                    println!("day {day} part {part}: ERROR {error}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+    if record && missing > 0 {
+        answers.save(answers_path)?;
+        println!("recorded {missing} answers in {answers_path}");
+    }
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{failed} answers failed the check")),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn to_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_positional() {
+        let args = Args::parse(&to_args(&["6", "2"])).unwrap();
+        let expected = Args {
+            selector: Selector::Day {
+                day: 6,
+                part: Some(2),
+            },
+            input: InputOptions::default(),
+            mode: Mode::Solve,
+            format: Format::Text,
+            workers: None,
+        };
+        assert_eq!(args, expected);
+    }
+
+    #[test]
+    fn parse_flags() {
+        let args = Args::parse(&to_args(&[
+            "--day", "9", "--part", "1", "--input", "in.txt",
+        ]));
+        let expected = Args {
+            selector: Selector::Day {
+                day: 9,
+                part: Some(1),
+            },
+            input: InputOptions {
+                dir: None,
+                path: Some("in.txt".into()),
+            },
+            mode: Mode::Solve,
+            format: Format::Text,
+            workers: None,
+        };
+        assert_eq!(args, Ok(expected));
+    }
+
+    #[test]
+    fn parse_module_name() {
+        let args = Args::parse(&to_args(&["template"])).unwrap();
+        assert_eq!(args.selector, Selector::Module("template".into()));
+        assert_eq!(args.input, InputOptions::default());
+    }
+
+    #[test]
+    fn parse_input_location() {
+        let args = Args::parse(&to_args(&["5", "-"])).unwrap();
+        assert_eq!(args.input.path.as_deref(), Some("-"));
+        let args = Args::parse(&to_args(&["day5", "--input-dir", "inputs"])).unwrap();
+        assert_eq!(args.input.dir.as_deref(), Some("inputs"));
+        assert_eq!(args.input.path, None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Args::parse(&to_args(&[])).is_err());
+        assert!(Args::parse(&to_args(&["--day"])).is_err());
+        assert!(Args::parse(&to_args(&["--day", "x"])).is_err());
+        assert!(Args::parse(&to_args(&["--verbose"])).is_err());
+        assert!(Args::parse(&to_args(&["1", "2", "in.txt", "extra"])).is_err());
+    }
+
+    #[test]
+    fn parse_check() {
+        let args = Args::parse(&to_args(&["--check"])).unwrap();
+        assert_eq!(args.selector, Selector::All);
+        let expected = Mode::Check {
+            answers: check::ANSWERS_PATH.into(),
+            record: false,
+        };
+        assert_eq!(args.mode, expected);
+        let args = Args::parse(&to_args(&["3", "--record", "--answers", "a.txt"])).unwrap();
+        assert_eq!(args.selector, Selector::Day { day: 3, part: None });
+        let expected = Mode::Check {
+            answers: "a.txt".into(),
+            record: true,
+        };
+        assert_eq!(args.mode, expected);
+        assert!(Args::parse(&to_args(&["--check", "day6_part2"])).is_err());
+        assert!(Args::parse(&to_args(&["--check", "--input", "in.txt"])).is_err());
+    }
+
+    #[test]
+    fn parse_bench() {
+        let args = Args::parse(&to_args(&["--bench", "--runs", "10"])).unwrap();
+        assert_eq!(args.selector, Selector::All);
+        assert_eq!(
+            args.mode,
+            Mode::Bench(BenchOptions {
+                runs: 10,
+                warmup: 1
+            })
+        );
+        let args = Args::parse(&to_args(&["6", "--warmup", "0"])).unwrap();
+        assert_eq!(args.mode, Mode::Bench(BenchOptions { runs: 5, warmup: 0 }));
+        assert!(Args::parse(&to_args(&["--bench", "--check"])).is_err());
+        assert!(Args::parse(&to_args(&["--bench", "--runs", "x"])).is_err());
+        assert!(Args::parse(&to_args(&["--bench", "--runs", "0"])).is_err());
+    }
+
+    #[test]
+    fn parse_render() {
+        let args = Args::parse(&to_args(&["6", "--render"])).unwrap();
+        assert_eq!(args.mode, Mode::Render(RenderOptions::default()));
+        let args = Args::parse(&to_args(&["6", "--every", "100", "--no-colour"])).unwrap();
+        assert_eq!(
+            args.mode,
+            Mode::Render(RenderOptions {
+                every: Some(100),
+                colour: false,
+                ..RenderOptions::default()
+            })
+        );
+        let args = Args::parse(&to_args(&["9", "--ppm", "frames", "--scale", "2"])).unwrap();
+        assert_eq!(
+            args.mode,
+            Mode::Render(RenderOptions {
+                ppm: Some("frames".into()),
+                scale: 2,
+                ..RenderOptions::default()
+            })
+        );
+        assert!(Args::parse(&to_args(&["9", "--ppm"])).is_err());
+        assert!(Args::parse(&to_args(&["9", "--scale", "0"])).is_err());
+        assert!(Args::parse(&to_args(&["6", "--render", "--every", "0"])).is_err());
+        assert!(Args::parse(&to_args(&["6", "2", "--render"])).is_err());
+        assert!(Args::parse(&to_args(&["--render"])).is_err());
+        assert!(Args::parse(&to_args(&["day6", "--render"])).is_err());
+        assert!(Args::parse(&to_args(&["6", "--render", "--check"])).is_err());
+    }
+
+    #[test]
+    fn parse_format() {
+        let args = Args::parse(&to_args(&["7", "--format", "json"])).unwrap();
+        assert_eq!(args.format, Format::Json);
+        assert!(Args::parse(&to_args(&["7", "--format", "xml"])).is_err());
+        assert!(Args::parse(&to_args(&["day7", "--format", "json"])).is_err());
+        assert!(Args::parse(&to_args(&["--check", "--format", "json"])).is_err());
+    }
+
+    #[test]
+    fn parse_all() {
+        let args = Args::parse(&to_args(&["--all", "--workers", "3"])).unwrap();
+        assert_eq!(args.selector, Selector::All);
+        assert_eq!(args.mode, Mode::Solve);
+        assert_eq!(args.workers, Some(3));
+        assert_eq!(Args::parse(&to_args(&["-a"])).unwrap().workers, None);
+        assert!(Args::parse(&to_args(&["--all", "4"])).is_err());
+        assert!(Args::parse(&to_args(&["4", "--workers", "2"])).is_err());
+    }
+
+    #[test]
+    fn parse_new_day() {
+        let args = Args::parse(&to_args(&["new-day", "14"])).unwrap();
+        assert_eq!(args.mode, Mode::NewDay);
+        assert_eq!(
+            args.selector,
+            Selector::Day {
+                day: 14,
+                part: None
+            }
+        );
+        assert!(Args::parse(&to_args(&["new-day"])).is_err());
+        assert!(Args::parse(&to_args(&["new-day", "14", "2"])).is_err());
+    }
+
+    #[test]
+    fn parse_generate() {
+        let args = Args::parse(&to_args(&["generate", "9", "--size", "100000x1"])).unwrap();
+        assert_eq!(args.selector, Selector::Day { day: 9, part: None });
+        assert_eq!(
+            args.mode,
+            Mode::Generate {
+                shape: Some(Shape {
+                    width: 100000,
+                    height: 1
+                }),
+                seed: generate::DEFAULT_SEED
+            }
+        );
+        let args = Args::parse(&to_args(&["generate", "--seed", "7", "12"])).unwrap();
+        assert_eq!(
+            args.mode,
+            Mode::Generate {
+                shape: None,
+                seed: 7
+            }
+        );
+        assert!(Args::parse(&to_args(&["generate"])).is_err());
+        assert!(Args::parse(&to_args(&["generate", "12", "13"])).is_err());
+        assert!(Args::parse(&to_args(&["generate", "12", "--size", "big"])).is_err());
+    }
+
+    #[test]
+    fn parse_serve() {
+        let args = Args::parse(&to_args(&["serve"])).unwrap();
+        assert_eq!(
+            args.mode,
+            Mode::Serve {
+                address: server::DEFAULT_ADDRESS.into()
+            }
+        );
+        assert_eq!(args.workers, None);
+        let args = Args::parse(&to_args(&["serve", "--address", "0.0.0.0:3000", "-w", "2"]));
+        let args = args.unwrap();
+        assert_eq!(
+            args.mode,
+            Mode::Serve {
+                address: "0.0.0.0:3000".into()
+            }
+        );
+        assert_eq!(args.workers, Some(2));
+        assert!(Args::parse(&to_args(&["serve", "--address"])).is_err());
+        assert!(Args::parse(&to_args(&["serve", "6"])).is_err());
+    }
+
+    #[test]
+    fn parse_fetch() {
+        let args = Args::parse(&to_args(&["fetch", "--day", "14"])).unwrap();
+        assert_eq!(
+            args.selector,
+            Selector::Day {
+                day: 14,
+                part: None
+            }
+        );
+        assert_eq!(
+            args.mode,
+            Mode::Fetch {
+                session_file: fetch::SESSION_FILE.into()
+            }
+        );
+        let args = Args::parse(&to_args(&[
+            "fetch",
+            "3",
+            "--input-dir",
+            "inputs",
+            "--session-file",
+            "token.txt",
+        ]))
+        .unwrap();
+        assert_eq!(args.input.dir.as_deref(), Some("inputs"));
+        assert_eq!(
+            args.mode,
+            Mode::Fetch {
+                session_file: "token.txt".into()
+            }
+        );
+        assert!(Args::parse(&to_args(&["fetch"])).is_err());
+        assert!(Args::parse(&to_args(&["fetch", "26"])).is_err());
+        assert!(Args::parse(&to_args(&["fetch", "3", "--input", "in.txt"])).is_err());
+    }
+
+    #[test]
+    fn find_by_day() {
+        assert_eq!(find_day(6).unwrap().day, 6);
+        assert_eq!(find_day(13).unwrap().day, 13);
+    }
+
+    #[test]
+    fn every_module_is_reachable() {
+        for module in MODULES {
+            assert_eq!(find_module(module.name).unwrap().name, module.name);
+        }
+    }
+
+    #[test]
+    fn render_days() {
+        let rendered: Vec<u8> = DAYS
+            .iter()
+            .filter(|entry| entry.render.is_some())
+            .map(|entry| entry.day)
+            .collect();
+        assert_eq!(rendered, [6, 8, 9, 10, 11, 12]);
+        let render = find_day(12).unwrap().render.unwrap();
+        let mut frames = Vec::new();
+        render("AAB\nAAB", None, &mut |canvas, _| {
+            frames.push(render::draw(canvas, false))
+        })
+        .unwrap();
+        assert_eq!(frames, ["AAB\nAAB\n"]);
+        assert!(render("AA\nA", None, &mut |_, _| {}).is_err());
+    }
+
+    #[test]
+    fn unknown_day() {
+        assert!(find_day(42).unwrap_err().contains("unknown day 42"));
+        assert!(find_day(0).is_err());
+        assert!(find_module("day99").is_err());
+    }
+
+    #[test]
+    fn solve_example() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+        let day = find_day(1).unwrap();
+        assert_eq!((day.solve)(input, 1), Ok("11".to_string()));
+        assert_eq!((day.solve)(input, 2), Ok("31".to_string()));
+        assert!((day.solve)(input, 3).is_err());
+    }
+}
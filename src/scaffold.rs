@@ -0,0 +1,218 @@
+use crate::fixture::{self, ANSWERS_EXTENSION};
+use crate::input;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("template.rs");
+const LINE_WIDTH: usize = 100;
+
+// creates src/dayN.rs from the template and registers it, `root` is the crate directory
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let module_path = root.join(format!("src/day{day}.rs"));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+    let lib_path = root.join("src/lib.rs");
+    let runner_path = root.join("src/runner.rs");
+    let lib = register_module(&read(&lib_path)?, day)?;
+    let runner = register_runner(&read(&runner_path)?, day)?;
+
+    let input_path = root.join(input::DEFAULT_DIR).join(input::file_name(day));
+    let fixture_path = root.join(fixture::day_dir(day)).join("example1.txt");
+    let answers_path = fixture_path.with_extension(ANSWERS_EXTENSION);
+    for path in [&input_path, &fixture_path, &answers_path] {
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+    }
+
+    write(&module_path, &render_day(day))?;
+    write(&lib_path, &lib)?;
+    write(&runner_path, &runner)?;
+    fs::create_dir_all(root.join(fixture::day_dir(day)))
+        .map_err(|error| format!("can't create {}: {error}", fixture::day_dir(day)))?;
+    write(&input_path, "")?;
+    write(&fixture_path, "")?;
+    write(&answers_path, "# part answer\n1 expected answer\n")?;
+    Ok(vec![
+        module_path,
+        lib_path,
+        runner_path,
+        input_path,
+        fixture_path,
+        answers_path,
+    ])
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|error| format!("can't read {}: {error}", path.display()))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|error| format!("can't write {}: {error}", path.display()))
+}
+
+fn render_day(day: u8) -> String {
+    let source = TEMPLATE
+        .replace("const DAY: u8 = 0;", &format!("const DAY: u8 = {day};"))
+        .replace("Template", &format!("Day{day}"));
+    let end = source.trim_end().len() - 1;
+    let example_test = r#"
+    #[test]
+    fn examples() {
+        crate::fixture::check_day(DAY).unwrap();
+    }
+"#;
+    format!("{}{example_test}}}\n", &source[..end])
+}
+
+fn register_module(lib: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{day}");
+    let new_line = format!("pub mod {name};");
+    let mut lines: Vec<&str> = lib.lines().collect();
+    if lines.contains(&new_line.as_str()) {
+        return Err(format!("day{day} is already declared in lib.rs"));
+    }
+    let first = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod "))
+        .ok_or("no modules in lib.rs")?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| line.starts_with("pub mod "))
+        .count();
+    let index = first
+        + lines[first..first + count]
+            .iter()
+            .take_while(|line| module_name(line) < name.as_str())
+            .count();
+    lines.insert(index, &new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn module_name(line: &str) -> &str {
+    line.trim_start_matches("pub mod ").trim_end_matches(';')
+}
+
+fn register_runner(runner: &str, day: u8) -> Result<String, String> {
+    let runner = insert_before_end(
+        runner,
+        "pub const MODULES: &[Module] = &[\n",
+        &format!("    Module::new(\"day{day}\", {day}, day{day}::main),\n"),
+    )?;
+    let runner = insert_before_end(
+        &runner,
+        "pub const DAYS: &[Day] = &[\n",
+        &format!("    Day::new::<day{day}::Day{day}>({day}),\n"),
+    )?;
+
+    let start = runner
+        .find("use crate::{")
+        .ok_or("no module imports in runner.rs")?;
+    let end = start
+        + runner[start..]
+            .find("};\n")
+            .ok_or("unclosed imports in runner.rs")?
+        + 3;
+    let inner = &runner[start + "use crate::{".len()..end - 3];
+    let mut modules: Vec<String> = inner
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .map(str::to_owned)
+        .collect();
+    modules.push(format!("day{day}"));
+    modules.sort_by_key(|module| version_key(module));
+    Ok(format!(
+        "{}{}{}",
+        &runner[..start],
+        format_use_list(&modules),
+        &runner[end..]
+    ))
+}
+
+fn insert_before_end(source: &str, start: &str, line: &str) -> Result<String, String> {
+    let list = source
+        .find(start)
+        .ok_or(format!("can't find {:?}", start.trim()))?;
+    let end = list + source[list..].find("];\n").ok_or("unclosed list")?;
+    Ok(format!("{}{line}{}", &source[..end], &source[end..]))
+}
+
+// sorts day2 before day10 the way rustfmt does
+fn version_key(module: &str) -> (String, u32, String) {
+    let prefix: String = module.chars().take_while(|c| !c.is_ascii_digit()).collect();
+    let rest = &module[prefix.len()..];
+    let digits: String = rest.chars().take_while(char::is_ascii_digit).collect();
+    let suffix = rest[digits.len()..].to_owned();
+    (prefix, digits.parse().unwrap_or(0), suffix)
+}
+
+fn format_use_list(modules: &[String]) -> String {
+    let single = format!("use crate::{{{}}};\n", modules.join(", "));
+    if single.len() <= LINE_WIDTH + 1 {
+        return single;
+    }
+    let mut lines = vec![String::from("   ")];
+    for module in modules {
+        let last = lines.last_mut().unwrap();
+        if last.len() + module.len() + 2 > LINE_WIDTH {
+            lines.push(String::from("   "));
+        }
+        let last = lines.last_mut().unwrap();
+        last.push(' ');
+        last.push_str(module);
+        last.push(',');
+    }
+    format!("use crate::{{\n{}\n}};\n", lines.join("\n"))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template() {
+        let source = render_day(14);
+        assert!(source.contains("const DAY: u8 = 14;"));
+        assert!(source.contains("pub struct Day14;"));
+        assert!(source.contains("impl Solution for Day14 {"));
+        assert!(!source.contains("Template"));
+        assert!(source.contains("crate::fixture::check_day(DAY)"));
+        assert!(!source.contains("todo!"));
+        assert!(source.ends_with("    }\n}\n"));
+    }
+
+    #[test]
+    fn register_in_lib() {
+        let lib = "pub mod check;\npub mod day1;\npub mod day13;\npub mod day2;\npub mod error;\n\npub use error::ParseError;\n";
+        let expected = "pub mod check;\npub mod day1;\npub mod day13;\npub mod day14;\npub mod day2;\npub mod error;\n\npub use error::ParseError;\n";
+        assert_eq!(register_module(lib, 14), Ok(expected.to_string()));
+        assert!(register_module(lib, 13).is_err());
+    }
+
+    #[test]
+    fn register_in_runner() {
+        let runner = include_str!("runner.rs");
+        let registered = register_runner(runner, 14).unwrap();
+        assert!(registered.contains("    Module::new(\"day14\", 14, day14::main),\n];"));
+        assert!(registered.contains("    Day::new::<day14::Day14>(14),\n];"));
+        assert!(registered.contains(" day13, day14,"));
+        for line in registered.lines() {
+            assert!(line.len() <= LINE_WIDTH, "{line}");
+        }
+    }
+
+    #[test]
+    fn refuse_to_overwrite() {
+        let error = new_day(Path::new(env!("CARGO_MANIFEST_DIR")), 1).unwrap_err();
+        assert!(error.ends_with("day1.rs already exists"), "{error}");
+    }
+
+    #[test]
+    fn sort_like_rustfmt() {
+        let mut modules = ["day10", "day2", "template", "day2_part2", "day1"];
+        modules.sort_by_key(|module| version_key(module));
+        assert_eq!(modules, ["day1", "day2", "day2_part2", "day10", "template"]);
+    }
+}
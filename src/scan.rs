@@ -0,0 +1,397 @@
+use crate::error::ParseError;
+
+// a line format like "Button {word}: X{int}, Y{int}" where
+//   {int} is a number with an optional sign, {uint} a number without one,
+//   {word} letters, digits and underscores, {char} any single character,
+//   {int+,} one or more ints separated by "," (any kind and separator can repeat),
+//   whitespace matches any amount of whitespace, including none,
+//   {{ and }} are literal braces
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    day: u8,
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Literal(String),
+    Space,
+    Capture(Kind),
+    Repeat(Kind, Vec<Piece>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Int,
+    Uint,
+    Word,
+    Char,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Int(i64),
+    Uint(u64),
+    Word(&'a str),
+    Char(char),
+    List(Vec<Capture<'a>>),
+}
+
+// a value with the text it was read from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture<'a> {
+    pub value: Value<'a>,
+    pub text: &'a str,
+}
+
+impl Kind {
+    fn parse(name: &str) -> Option<Kind> {
+        match name {
+            "int" => Some(Kind::Int),
+            "uint" => Some(Kind::Uint),
+            "word" => Some(Kind::Word),
+            "char" => Some(Kind::Char),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Int => "int",
+            Kind::Uint => "uint",
+            Kind::Word => "word",
+            Kind::Char => "char",
+        }
+    }
+}
+
+impl Pattern {
+    // panics on a malformed pattern, patterns are part of the code
+    pub fn new(day: u8, pattern: &str) -> Pattern {
+        let pieces = parse_pattern(pattern).unwrap_or_else(|error| panic!("{pattern:?}: {error}"));
+        Pattern { day, pieces }
+    }
+
+    // matches the whole line, surrounding whitespace is ignored
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Vec<Capture<'a>>, ParseError> {
+        let mut captures = Vec::new();
+        let rest = match_pieces(&self.pieces, line.trim(), &mut captures)
+            .map_err(|(fragment, message)| ParseError::new(self.day, line, fragment, message))?;
+        if !rest.is_empty() {
+            return Err(ParseError::new(self.day, line, rest, "unexpected text"));
+        }
+        Ok(captures)
+    }
+
+    // the captures converted to a tuple of types, like (i64, Vec<u32>)
+    pub fn scan<'a, T: FromCaptures<'a>>(&self, line: &'a str) -> Result<T, ParseError> {
+        let captures = self.captures(line)?;
+        T::from_captures(&captures)
+            .map_err(|(fragment, message)| ParseError::new(self.day, line, fragment, message))
+    }
+}
+
+// errors are (where the match failed, what was expected)
+fn match_pieces<'a>(
+    pieces: &[Piece],
+    mut rest: &'a str,
+    captures: &mut Vec<Capture<'a>>,
+) -> Result<&'a str, (&'a str, String)> {
+    for piece in pieces {
+        rest = match piece {
+            Piece::Literal(literal) => rest
+                .strip_prefix(literal.as_str())
+                .ok_or_else(|| (rest, format!("expected {literal:?}")))?,
+            Piece::Space => rest.trim_start(),
+            Piece::Capture(kind) => {
+                let (capture, after) = capture(*kind, rest)?;
+                captures.push(capture);
+                after
+            }
+            Piece::Repeat(kind, separator) => {
+                let (first, mut after) = capture(*kind, rest)?;
+                let mut items = vec![first];
+                // stops before a separator that isn't followed by another item, an item that
+                // is there but invalid is an error
+                while let Ok(next) = match_pieces(separator, after, &mut Vec::new())
+                    && !token(*kind, next).0.is_empty()
+                {
+                    let (item, next) = capture(*kind, next)?;
+                    items.push(item);
+                    after = next;
+                }
+                let text = &rest[..rest.len() - after.len()];
+                captures.push(Capture {
+                    value: Value::List(items),
+                    text,
+                });
+                after
+            }
+        };
+    }
+    Ok(rest)
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("unmatched }".to_string()),
+            '{' => {
+                push_literal(&mut pieces, &mut literal);
+                let mut spec = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => return Err("unclosed {".to_string()),
+                    }
+                }
+                pieces.push(parse_capture(&spec)?);
+            }
+            c if c.is_whitespace() => {
+                push_literal(&mut pieces, &mut literal);
+                if pieces.last() != Some(&Piece::Space) {
+                    pieces.push(Piece::Space);
+                }
+            }
+            c => literal.push(c),
+        }
+    }
+    push_literal(&mut pieces, &mut literal);
+    Ok(pieces)
+}
+
+fn push_literal(pieces: &mut Vec<Piece>, literal: &mut String) {
+    if !literal.is_empty() {
+        pieces.push(Piece::Literal(std::mem::take(literal)));
+    }
+}
+
+fn parse_capture(spec: &str) -> Result<Piece, String> {
+    let (name, separator) = match spec.split_once('+') {
+        Some((name, separator)) => (name, Some(separator)),
+        None => (spec, None),
+    };
+    let kind = Kind::parse(name).ok_or_else(|| format!("unknown capture {{{spec}}}"))?;
+    match separator {
+        None => Ok(Piece::Capture(kind)),
+        Some(separator) if separator.contains(['{', '}']) => {
+            Err(format!("separator of {{{spec}}} can't contain braces"))
+        }
+        Some(separator) => Ok(Piece::Repeat(kind, parse_pattern(separator)?)),
+    }
+}
+
+// splits the text of a `kind` value from the start of `rest`, it's empty if there's none
+fn token(kind: Kind, rest: &str) -> (&str, &str) {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let length = match kind {
+        Kind::Char => rest.chars().next().map_or(0, char::len_utf8),
+        // a sign and everything that looks like part of the number, so "4o" is reported whole
+        Kind::Int | Kind::Uint => {
+            let sign = rest.starts_with(['+', '-']) as usize;
+            sign + rest[sign..]
+                .find(|c| !is_word(c))
+                .unwrap_or(rest.len() - sign)
+        }
+        Kind::Word => rest.find(|c| !is_word(c)).unwrap_or(rest.len()),
+    };
+    rest.split_at(length)
+}
+
+// reads one value of `kind` from the start of `rest`
+fn capture(kind: Kind, rest: &str) -> Result<(Capture<'_>, &str), (&str, String)> {
+    let (text, after) = token(kind, rest);
+    if text.is_empty() {
+        let found = match rest.chars().next() {
+            Some(c) => format!("{c:?}"),
+            None => "end of line".to_string(),
+        };
+        return Err((rest, format!("expected {}, found {found}", kind.name())));
+    }
+    let invalid = || (text, format!("invalid {}", kind.name()));
+    let value = match kind {
+        Kind::Int => Value::Int(text.parse().map_err(|_| invalid())?),
+        Kind::Uint => Value::Uint(text.parse().map_err(|_| invalid())?),
+        Kind::Word => Value::Word(text),
+        Kind::Char => Value::Char(text.chars().next().unwrap()),
+    };
+    Ok((Capture { value, text }, after))
+}
+
+// converts a capture into a Rust value, errors are (fragment, message)
+pub trait FromCapture<'a>: Sized {
+    fn from_capture(capture: &Capture<'a>) -> Result<Self, (&'a str, String)>;
+}
+
+macro_rules! from_number {
+    ($($t:ty),*) => {$(
+        impl<'a> FromCapture<'a> for $t {
+            fn from_capture(capture: &Capture<'a>) -> Result<$t, (&'a str, String)> {
+                let out_of_range = || (capture.text, format!("out of range for {}", stringify!($t)));
+                match capture.value {
+                    Value::Int(n) => <$t>::try_from(n).map_err(|_| out_of_range()),
+                    Value::Uint(n) => <$t>::try_from(n).map_err(|_| out_of_range()),
+                    _ => Err((capture.text, "expected a number".to_string())),
+                }
+            }
+        }
+    )*};
+}
+
+from_number!(i32, i64, u8, u32, u64, usize);
+
+impl<'a> FromCapture<'a> for &'a str {
+    fn from_capture(capture: &Capture<'a>) -> Result<&'a str, (&'a str, String)> {
+        Ok(capture.text)
+    }
+}
+
+impl<'a> FromCapture<'a> for String {
+    fn from_capture(capture: &Capture<'a>) -> Result<String, (&'a str, String)> {
+        Ok(capture.text.to_string())
+    }
+}
+
+impl<'a> FromCapture<'a> for char {
+    fn from_capture(capture: &Capture<'a>) -> Result<char, (&'a str, String)> {
+        match capture.value {
+            Value::Char(c) => Ok(c),
+            _ => Err((capture.text, "expected a single character".to_string())),
+        }
+    }
+}
+
+impl<'a, T: FromCapture<'a>> FromCapture<'a> for Vec<T> {
+    fn from_capture(capture: &Capture<'a>) -> Result<Vec<T>, (&'a str, String)> {
+        match &capture.value {
+            Value::List(items) => items.iter().map(T::from_capture).collect(),
+            _ => Err((capture.text, "expected a list".to_string())),
+        }
+    }
+}
+
+// every capture of a pattern at once, implemented for tuples
+pub trait FromCaptures<'a>: Sized {
+    fn from_captures(captures: &[Capture<'a>]) -> Result<Self, (&'a str, String)>;
+}
+
+macro_rules! from_captures {
+    ($count:literal: $($t:ident),+) => {
+        impl<'a, $($t: FromCapture<'a>),+> FromCaptures<'a> for ($($t,)+) {
+            fn from_captures(captures: &[Capture<'a>]) -> Result<Self, (&'a str, String)> {
+                // a mismatch is a bug in the calling code, not in the input
+                assert_eq!(captures.len(), $count, "the pattern has {} captures", captures.len());
+                let mut captures = captures.iter();
+                Ok(($($t::from_capture(captures.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+from_captures!(1: A);
+from_captures!(2: A, B);
+from_captures!(3: A, B, C);
+from_captures!(4: A, B, C, D);
+from_captures!(5: A, B, C, D, E);
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_captures() {
+        let button = Pattern::new(13, "Button {word}: X{int}, Y{int}");
+        assert_eq!(
+            button.scan("  Button A: X+94, Y-34 "),
+            Ok(("A", 94i64, -34i32))
+        );
+        let pattern = Pattern::new(0, "{char}{uint} -> {word}");
+        assert_eq!(
+            pattern.scan("x12->abc_1"),
+            Ok(('x', 12u8, "abc_1".to_string()))
+        );
+        let braces = Pattern::new(0, "{{{int}}}");
+        assert_eq!(braces.scan("{-7}"), Ok((-7i64,)));
+    }
+
+    #[test]
+    fn repetition() {
+        let equation = Pattern::new(7, "{uint}: {uint+ }");
+        assert_eq!(
+            equation.scan("3267: 81   40 27"),
+            Ok((3267u64, vec![81u64, 40, 27]))
+        );
+        let update = Pattern::new(5, "{uint+,}");
+        assert_eq!(update.scan("75,47,61"), Ok((vec![75u32, 47, 61],)));
+        let pairs = Pattern::new(0, "{int+, } end");
+        let captures = pairs.captures("1, 2,3 end").unwrap();
+        assert_eq!(captures[0].text, "1, 2,3");
+        let (words,): (Vec<&str>,) = Pattern::new(0, "{word+ }").scan("a bc d").unwrap();
+        assert_eq!(words, ["a", "bc", "d"]);
+        let error = equation.scan::<(u64, Vec<u64>)>("3267: 81 4o").unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (10, "4o"));
+    }
+
+    #[test]
+    fn report_mismatch() {
+        let button = Pattern::new(13, "Button {word}: X{int}, Y{int}");
+        let error = button
+            .scan::<(&str, i64, i64)>("Button A: Z+94, Y+34")
+            .unwrap_err();
+        assert_eq!(
+            (error.day, error.column, error.text.as_str()),
+            (13, 11, "Z+94, Y+34")
+        );
+        assert_eq!(error.message, "expected \"X\"");
+        let error = button
+            .scan::<(&str, i64, i64)>("Button A: X+9x, Y+34")
+            .unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (12, "+9x"));
+        assert_eq!(error.message, "invalid int");
+        let error = button
+            .scan::<(&str, i64, i64)>("Button A: X+94, Y")
+            .unwrap_err();
+        assert_eq!(error.message, "expected int, found end of line");
+        let error = button
+            .scan::<(&str, i64, i64)>("Button A: X+94, Y+34!")
+            .unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (21, "unexpected text")
+        );
+        let error = button
+            .scan::<(&str, u8, i64)>("Button A: X+300, Y+34")
+            .unwrap_err();
+        assert_eq!(
+            (error.text.as_str(), error.message.as_str()),
+            ("+300", "out of range for u8")
+        );
+        let error = button
+            .scan::<(&str, i64, i64)>("Button : X+94, Y+34")
+            .unwrap_err();
+        assert_eq!(error.message, "expected word, found ':'");
+        let uint = Pattern::new(0, "{uint}");
+        assert_eq!(
+            uint.scan::<(u64,)>("-1").unwrap_err().message,
+            "invalid uint"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown capture {number}")]
+    fn malformed_pattern() {
+        Pattern::new(0, "{number}");
+    }
+}
@@ -0,0 +1,374 @@
+use crate::pool::{self, ThreadPool};
+use crate::report::{Format, PartResult, json_string};
+use crate::runner::{self, DAYS};
+use crate::solution::SolveError;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+// the largest puzzle input accepted, the real ones are around 20 KiB
+const MAX_BODY: usize = 16 << 20;
+// a client that stops sending doesn't hold a worker forever
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Response {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!(r#"{{"error":{}}}"#, json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn to_http(&self) -> String {
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    // the request line, the headers and a body of Content-Length bytes
+    pub fn read(reader: &mut impl BufRead) -> Result<Request, Response> {
+        let malformed = |what: &str| Response::error(400, &format!("malformed request: {what}"));
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|_| malformed("can't read the request line"))?;
+        let mut words = line.split_whitespace();
+        let (Some(method), Some(path), Some(version), None) =
+            (words.next(), words.next(), words.next(), words.next())
+        else {
+            return Err(malformed("expected <method> <path> <version>"));
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(malformed("expected HTTP/1.x"));
+        }
+        let (method, path) = (method.to_owned(), path.to_owned());
+
+        let mut length = None;
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => return Err(malformed("the headers don't end")),
+                Ok(_) => {}
+                Err(_) => return Err(malformed("can't read the headers")),
+            }
+            let header = line.trim_end_matches(['\r', '\n']);
+            if header.is_empty() {
+                break;
+            }
+            let (name, value) = header
+                .split_once(':')
+                .ok_or_else(|| malformed("header without a colon"))?;
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                let value: usize = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| malformed("invalid Content-Length"))?;
+                length = Some(value);
+            }
+        }
+
+        let length = match length {
+            Some(length) if length > MAX_BODY => {
+                return Err(Response::error(
+                    413,
+                    &format!("the body is larger than {MAX_BODY} bytes"),
+                ));
+            }
+            Some(length) => length,
+            None if method == "POST" => {
+                return Err(Response::error(411, "a POST needs a Content-Length"));
+            }
+            None => 0,
+        };
+        let mut body = vec![0; length];
+        reader
+            .read_exact(&mut body)
+            .map_err(|_| malformed("the body is shorter than its Content-Length"))?;
+        Ok(Request { method, path, body })
+    }
+}
+
+// GET /days lists the days, POST /day/<day>/part/<part> solves the body
+pub fn respond(request: &Request) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["days"]) => days(),
+        ("POST", ["day", day, "part", part]) => solve(day, part, &request.body),
+        (_, ["days"]) => Response::error(405, "/days only supports GET"),
+        (_, ["day", _, "part", _]) => Response::error(405, "solving only supports POST"),
+        _ => Response::error(404, &format!("no route for {}", request.path)),
+    }
+}
+
+fn days() -> Response {
+    let days: Vec<String> = DAYS.iter().map(|entry| entry.day.to_string()).collect();
+    Response::ok(format!(r#"{{"days":[{}]}}"#, days.join(",")))
+}
+
+fn solve(day: &str, part: &str, body: &[u8]) -> Response {
+    let Ok(day) = day.parse() else {
+        return Response::error(404, &format!("invalid day {day:?}"));
+    };
+    let entry = match runner::find_day(day) {
+        Ok(entry) => entry,
+        Err(error) => return Response::error(404, &error),
+    };
+    let part = match part.parse() {
+        Ok(part @ (1 | 2)) => part,
+        _ => return Response::error(404, &format!("invalid part {part:?}, expected 1 or 2")),
+    };
+    let Ok(input) = str::from_utf8(body) else {
+        return Response::error(400, "the input isn't valid UTF-8");
+    };
+
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| (entry.try_solve)(input, part)));
+    let time = start.elapsed();
+    let status = match &answer {
+        Ok(Ok(_)) => 200,
+        Ok(Err(SolveError::Parse(_))) => 400,
+        Ok(Err(SolveError::InvalidPart(_))) => 404,
+        Ok(Err(SolveError::Unsolvable(_))) => 422,
+        Err(_) => 500,
+    };
+    let answer = match answer {
+        Ok(answer) => answer.map_err(|error| error.to_string()),
+        Err(payload) => Err(pool::panic_message(payload)),
+    };
+    let result = PartResult {
+        day,
+        part,
+        answer,
+        time,
+    };
+    Response {
+        status,
+        body: result.format(Format::Json),
+    }
+}
+
+fn handle(stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+    let response = match Request::read(&mut reader) {
+        Ok(request) => respond(&request),
+        Err(response) => response,
+    };
+    // the client may already be gone, there is no one left to tell
+    let _ = (&stream).write_all(response.to_http().as_bytes());
+}
+
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    pool: ThreadPool,
+}
+
+impl Server {
+    pub fn bind(address: &str, workers: usize) -> Result<Server, String> {
+        let listener =
+            TcpListener::bind(address).map_err(|error| format!("can't bind {address}: {error}"))?;
+        Ok(Server {
+            listener,
+            pool: ThreadPool::new(workers),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, String> {
+        self.listener
+            .local_addr()
+            .map_err(|error| format!("can't get the server address: {error}"))
+    }
+
+    // serves connections until the listener fails
+    pub fn run(&self) -> Result<(), String> {
+        for stream in self.listener.incoming() {
+            match stream {
+                Ok(stream) => self.pool.execute(move || handle(stream)),
+                Err(error) => eprintln!("can't accept a connection: {error}"),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::thread;
+
+    const DAY1: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".into(),
+            path: path.into(),
+            body: body.into(),
+        }
+    }
+
+    // a local client, returns the status line and the body
+    fn send(address: SocketAddr, request: &str) -> (String, String) {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_owned(), body.to_owned())
+    }
+
+    fn start() -> SocketAddr {
+        let server = Server::bind("127.0.0.1:0", 4).unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+        address
+    }
+
+    #[test]
+    fn read_request() {
+        let raw = "POST /day/1/part/2 HTTP/1.1\r\nHost: x\r\ncontent-length: 5\r\n\r\n1   2extra";
+        let request = Request::read(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request, post("/day/1/part/2", "1   2"));
+        let no_length = "POST /day/1/part/2 HTTP/1.1\r\n\r\n";
+        assert_eq!(
+            Request::read(&mut no_length.as_bytes()).unwrap_err().status,
+            411
+        );
+        let too_large = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        assert_eq!(
+            Request::read(&mut too_large.as_bytes()).unwrap_err().status,
+            413
+        );
+        for raw in [
+            "GET\r\n\r\n",
+            "GET / SMTP\r\n\r\n",
+            "GET / HTTP/1.1\r\nHost",
+        ] {
+            assert_eq!(Request::read(&mut raw.as_bytes()).unwrap_err().status, 400);
+        }
+    }
+
+    #[test]
+    fn routes() {
+        let days = respond(&Request {
+            method: "GET".into(),
+            path: "/days".into(),
+            body: Vec::new(),
+        });
+        assert_eq!(
+            days,
+            Response::ok(r#"{"days":[1,2,3,4,5,6,7,8,9,10,11,12,13]}"#.into())
+        );
+        let answer = respond(&post("/day/1/part/1", DAY1));
+        assert_eq!(answer.status, 200);
+        assert!(
+            answer
+                .body
+                .starts_with(r#"{"day":1,"part":1,"answer":"11","#)
+        );
+        assert_eq!(respond(&post("/days", "")).status, 405);
+        assert_eq!(respond(&post("/day/42/part/1", DAY1)).status, 404);
+        assert_eq!(respond(&post("/day/1/part/3", DAY1)).status, 404);
+        assert_eq!(respond(&post("/day/x/part/1", DAY1)).status, 404);
+        assert_eq!(respond(&post("/solve", DAY1)).status, 404);
+    }
+
+    #[test]
+    fn parse_errors_are_bad_requests() {
+        let response = respond(&post("/day/1/part/1", "3   4\n4   x"));
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains(r#""answer":null"#));
+        assert!(response.body.contains("day 1, line 2"));
+        let response = respond(&Request {
+            body: vec![0xff],
+            ..post("/day/1/part/1", "")
+        });
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn local_client() {
+        let address = start();
+        let (status, body) = send(address, "GET /days HTTP/1.1\r\n\r\n");
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert!(body.starts_with(r#"{"days":[1,2,"#));
+        let request = format!(
+            "POST /day/1/part/2 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{DAY1}",
+            DAY1.len()
+        );
+        let (status, body) = send(address, &request);
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert!(body.contains(r#""answer":"31""#));
+        let (status, body) = send(
+            address,
+            "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 3\r\n\r\n1 x",
+        );
+        assert_eq!(status, "HTTP/1.1 400 Bad Request");
+        assert!(body.contains(r#""error":"day 1, line 1"#));
+    }
+
+    #[test]
+    fn concurrent_clients() {
+        let address = start();
+        let clients: Vec<_> = (0..16)
+            .map(|client| {
+                thread::spawn(move || {
+                    let part = client % 2 + 1;
+                    let request = format!(
+                        "POST /day/1/part/{part} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{DAY1}",
+                        DAY1.len()
+                    );
+                    (part, send(address, &request))
+                })
+            })
+            .collect();
+        for client in clients {
+            let (part, (status, body)) = client.join().unwrap();
+            assert_eq!(status, "HTTP/1.1 200 OK");
+            let expected = if part == 1 { "11" } else { "31" };
+            assert!(body.contains(&format!(r#""answer":"{expected}""#)));
+        }
+    }
+}
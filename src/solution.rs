@@ -0,0 +1,96 @@
+use crate::answer::{Answer, Unsolvable};
+use crate::error::ParseError;
+use crate::input::normalize;
+use std::fmt::{self, Display, Formatter};
+
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Result<Answer, Unsolvable>;
+    fn part2(input: &Self::Input) -> Result<Answer, Unsolvable>;
+}
+
+// why a part has no answer, callers that only print it can use `solve`
+#[derive(Debug, PartialEq, Clone)]
+pub enum SolveError {
+    Parse(ParseError),
+    InvalidPart(u8),
+    Unsolvable(Unsolvable),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SolveError::Parse(error) => write!(f, "{error}"),
+            SolveError::InvalidPart(part) => write!(f, "invalid part {part}, expected 1 or 2"),
+            SolveError::Unsolvable(unsolvable) => write!(f, "{unsolvable}"),
+        }
+    }
+}
+
+pub fn try_solve<S: Solution>(input: &str, part: u8) -> Result<String, SolveError> {
+    let parsed = S::parse(&normalize(input)).map_err(SolveError::Parse)?;
+    let answer = match part {
+        1 => S::part1(&parsed),
+        2 => S::part2(&parsed),
+        _ => return Err(SolveError::InvalidPart(part)),
+    };
+    answer
+        .map(|answer| answer.to_string())
+        .map_err(SolveError::Unsolvable)
+}
+
+pub fn solve<S: Solution>(input: &str, part: u8) -> Result<String, String> {
+    try_solve::<S>(input, part).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::answer::CheckedIterator;
+
+    struct Sum;
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+
+        fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+            input
+                .split(',')
+                .map(|s| {
+                    s.parse()
+                        .map_err(|_| ParseError::new(0, input, s, "invalid number"))
+                })
+                .collect()
+        }
+        fn part1(input: &Vec<i32>) -> Result<Answer, Unsolvable> {
+            Ok(input.iter().copied().checked_sum("sum")?.into())
+        }
+        fn part2(input: &Vec<i32>) -> Result<Answer, Unsolvable> {
+            Ok(input.iter().copied().checked_product("product")?.into())
+        }
+    }
+
+    #[test]
+    fn solve_each_part() {
+        assert_eq!(solve::<Sum>("2,3,4", 1), Ok("9".to_string()));
+        assert_eq!(solve::<Sum>("2,3,4", 2), Ok("24".to_string()));
+        assert!(solve::<Sum>("2,3,4", 3).is_err());
+        assert_eq!(
+            solve::<Sum>("2,x,4", 1),
+            Err("day 0, line 1, column 3: invalid number \"x\"".to_string())
+        );
+        assert_eq!(
+            solve::<Sum>("65536,65536", 2),
+            Err("arithmetic overflow in product".to_string())
+        );
+        assert!(matches!(
+            try_solve::<Sum>("2,x,4", 1),
+            Err(SolveError::Parse(_))
+        ));
+        assert_eq!(
+            try_solve::<Sum>("2,3,4", 0),
+            Err(SolveError::InvalidPart(0))
+        );
+    }
+}
@@ -1,26 +1,60 @@
-use std::fs;
+use crate::answer::{Answer, Unsolvable};
+use crate::error::ParseError;
+use crate::solution::Solution;
 
-const FILE_NAME: &str = "input_day1.txt";
+const DAY: u8 = 0;
 
-pub fn main() {
+pub struct Template;
+
+impl Solution for Template {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_string(input)
+    }
+    // placeholders until the day is solved, the example test fails on them instead of panicking
+    fn part1(_input: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok("part 1 isn't solved yet".into())
+    }
+    fn part2(_input: &Self::Input) -> Result<Answer, Unsolvable> {
+        Ok("part 2 isn't solved yet".into())
+    }
+}
+
+pub fn main(input: &str) -> Result<(), ParseError> {
     println!("this is main");
-    let file_path = format!("artifacts/input_files/{}", FILE_NAME);
-    let input = fs::read_to_string(file_path).expect("Should have been able to read the file");
     println!("input: {:?}", input);
-    let parsed = parse_string(&input);
+    let parsed = Template::parse(input)?;
     println!("input parsed: {:?}", &parsed);
+    Ok(())
 }
 
-fn parse_string(input: &str) -> Vec<i32> {
-    let mut column1: Vec<i32> = Vec::new();
-    column1
+fn parse_string(input: &str) -> Result<Vec<i32>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.trim()
+                .parse()
+                .map_err(|_| ParseError::new(DAY, input, line, "invalid number"))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::solution::solve;
+    #[test]
+    fn placeholder_answers() {
+        assert_eq!(
+            solve::<Template>("1\n2", 1),
+            Ok("part 1 isn't solved yet".to_string())
+        );
+        assert!(solve::<Template>("1\n2", 2).is_ok());
+    }
     #[test]
     fn test_parse_string() {
-        println!("{}", "this is test dummy")
+        assert_eq!(parse_string(""), Ok(vec![]));
+        assert_eq!(parse_string("1\n-2"), Ok(vec![1, -2]));
     }
 }
@@ -0,0 +1,232 @@
+use crate::grid::Point;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign};
+
+// the signed integers a vector can be made of
+pub trait Integer:
+    Copy
+    + Debug
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+
+    fn abs(self) -> Self;
+    fn from_usize(n: usize) -> Option<Self>;
+    fn to_usize(self) -> Option<usize>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const ZERO: $t = 0;
+
+            fn abs(self) -> $t {
+                <$t>::abs(self)
+            }
+
+            fn from_usize(n: usize) -> Option<$t> {
+                <$t>::try_from(n).ok()
+            }
+
+            fn to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+        }
+    )*};
+}
+
+integer!(i32, i64);
+
+// always non-negative, gcd(0, 0) is 0
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct Vector2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct Vector3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// the same arithmetic for every dimension, component by component
+macro_rules! vector {
+    ($name:ident { $($c:ident),+ }) => {
+        impl<T: Integer> $name<T> {
+            pub const fn new($($c: T),+) -> $name<T> {
+                $name { $($c),+ }
+            }
+
+            // sum of the absolute components, the taxicab length
+            pub fn manhattan(self) -> T {
+                T::ZERO $(+ self.$c.abs())+
+            }
+
+            // largest absolute component, the number of king moves
+            pub fn chebyshev(self) -> T {
+                T::ZERO $(.max(self.$c.abs()))+
+            }
+
+            pub fn manhattan_distance(self, other: $name<T>) -> T {
+                (self - other).manhattan()
+            }
+
+            pub fn chebyshev_distance(self, other: $name<T>) -> T {
+                (self - other).chebyshev()
+            }
+
+            // the shortest vector with the same direction, the zero vector stays zero
+            pub fn reduced(self) -> $name<T> {
+                let divisor = [$(self.$c),+].into_iter().fold(T::ZERO, gcd);
+                if divisor == T::ZERO {
+                    return self;
+                }
+                $name { $($c: self.$c / divisor),+ }
+            }
+        }
+
+        impl<T: Integer> Add for $name<T> {
+            type Output = $name<T>;
+
+            fn add(self, other: $name<T>) -> $name<T> {
+                $name { $($c: self.$c + other.$c),+ }
+            }
+        }
+
+        impl<T: Integer> Sub for $name<T> {
+            type Output = $name<T>;
+
+            fn sub(self, other: $name<T>) -> $name<T> {
+                $name { $($c: self.$c - other.$c),+ }
+            }
+        }
+
+        impl<T: Integer> Neg for $name<T> {
+            type Output = $name<T>;
+
+            fn neg(self) -> $name<T> {
+                $name { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: Integer> Mul<T> for $name<T> {
+            type Output = $name<T>;
+
+            fn mul(self, scalar: T) -> $name<T> {
+                $name { $($c: self.$c * scalar),+ }
+            }
+        }
+
+        impl<T: Integer> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: $name<T>) {
+                *self = *self + other;
+            }
+        }
+
+        impl<T: Integer> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: $name<T>) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+vector!(Vector2 { x, y });
+vector!(Vector3 { x, y, z });
+
+impl<T: Integer> Vector2<T> {
+    // z of the 3D cross product, zero when the vectors are parallel
+    pub fn cross(self, other: Vector2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    // panics if the coordinates don't fit in T
+    pub fn from_point((x, y): Point) -> Vector2<T> {
+        let convert = |n| T::from_usize(n).unwrap_or_else(|| panic!("{n} doesn't fit"));
+        Vector2::new(convert(x), convert(y))
+    }
+
+    // None for negative coordinates
+    pub fn to_point(self) -> Option<Point> {
+        Some((self.x.to_usize()?, self.y.to_usize()?))
+    }
+
+    // None outside the width x height area starting at the origin
+    pub fn to_point_within(self, (width, height): (usize, usize)) -> Option<Point> {
+        self.to_point().filter(|&(x, y)| x < width && y < height)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vector2::new(3, -4);
+        let b = Vector2::new(1, 2);
+        assert_eq!(a + b, Vector2::new(4, -2));
+        assert_eq!(a - b, Vector2::new(2, -6));
+        assert_eq!(-a, Vector2::new(-3, 4));
+        assert_eq!(a * 3, Vector2::new(9, -12));
+        let mut c = a;
+        c += b;
+        c -= b * 2;
+        assert_eq!(c, Vector2::new(2, -6));
+        assert_eq!(a.cross(b), 10);
+        assert_eq!(a.cross(a * -2), 0);
+        let v: Vector3<i64> = Vector3::new(1, 2, 3) * 10_000_000_000;
+        assert_eq!(v - v, Vector3::default());
+    }
+
+    #[test]
+    fn distances() {
+        let a = Vector2::new(3, -4);
+        assert_eq!(a.manhattan(), 7);
+        assert_eq!(a.chebyshev(), 4);
+        assert_eq!(a.manhattan_distance(Vector2::new(-1, 1)), 9);
+        assert_eq!(a.chebyshev_distance(Vector2::new(-1, 1)), 5);
+        assert_eq!(Vector3::new(1i64, -2, 3).manhattan(), 6);
+        assert_eq!(Vector3::new(1i64, -2, 3).chebyshev(), 3);
+    }
+
+    #[test]
+    fn reduce() {
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(Vector2::new(4, -6).reduced(), Vector2::new(2, -3));
+        assert_eq!(Vector2::new(0, -5).reduced(), Vector2::new(0, -1));
+        assert_eq!(Vector2::new(0, 0).reduced(), Vector2::new(0, 0));
+        assert_eq!(Vector3::new(3i64, 6, 9).reduced(), Vector3::new(1, 2, 3));
+    }
+
+    #[test]
+    fn grid_points() {
+        let v: Vector2<i32> = Vector2::from_point((3, 4));
+        assert_eq!(v, Vector2::new(3, 4));
+        assert_eq!(v.to_point(), Some((3, 4)));
+        assert_eq!(Vector2::new(-1, 0).to_point(), None);
+        assert_eq!(v.to_point_within((4, 5)), Some((3, 4)));
+        assert_eq!(v.to_point_within((3, 5)), None);
+    }
+}